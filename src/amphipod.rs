@@ -0,0 +1,464 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cave_navigation::pathfinding::{dijkstra, Cost};
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Number of spaces in the hallway.
+const HALLWAY: usize = 11;
+/// Number of side rooms, one for each type of amphipod.
+const ROOMS: usize = 4;
+/// Deepest side rooms, after unfolding the diagram.
+const MAX_DEPTH: usize = 4;
+/// Rows inserted by unfolding the diagram, between the first and second
+/// row of the side rooms.
+const UNFOLDED: [[Amphipod; ROOMS]; 2] = [
+    [Amphipod::D, Amphipod::C, Amphipod::B, Amphipod::A],
+    [Amphipod::D, Amphipod::B, Amphipod::A, Amphipod::C],
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Amphipod {
+    A,
+    B,
+    C,
+    D,
+}
+
+/// Positions of all amphipods in the burrow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY],
+    /// `rooms[r][0]` is the space of side room `r` next to the hallway.
+    /// Only the first `depth` spaces of each room exist.
+    rooms: [[Option<Amphipod>; MAX_DEPTH]; ROOMS],
+    depth: usize,
+}
+
+impl Amphipod {
+    /// Energy needed to move one step.
+    pub fn energy(&self) -> Cost {
+        match self {
+            Amphipod::A => 1,
+            Amphipod::B => 10,
+            Amphipod::C => 100,
+            Amphipod::D => 1000,
+        }
+    }
+
+    /// Side room, which the amphipod belongs in.
+    fn room(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_char(char: char) -> Option<Amphipod> {
+        match char {
+            'A' => Some(Amphipod::A),
+            'B' => Some(Amphipod::B),
+            'C' => Some(Amphipod::C),
+            'D' => Some(Amphipod::D),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        (b'A' + self as u8) as char
+    }
+}
+
+/// Space of the hallway directly outside of side room `room`. Amphipods
+/// never stop there.
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn is_door(space: usize) -> bool {
+    (0..ROOMS).any(|room| door(room) == space)
+}
+
+impl Burrow {
+    /// The full diagram, with two more rows of amphipods in the side rooms.
+    /// Returns `None` unless the side rooms are 2 deep.
+    pub fn unfold(&self) -> Option<Burrow> {
+        if self.depth != 2 {
+            return None;
+        }
+
+        let mut unfolded = *self;
+        unfolded.depth = MAX_DEPTH;
+        for (room, spaces) in unfolded.rooms.iter_mut().enumerate() {
+            *spaces = [
+                self.rooms[room][0],
+                Some(UNFOLDED[0][room]),
+                Some(UNFOLDED[1][room]),
+                self.rooms[room][1],
+            ];
+        }
+
+        Some(unfolded)
+    }
+
+    /// True, if every amphipod is in its side room.
+    pub fn is_organized(&self) -> bool {
+        (0..ROOMS).all(|room| {
+            self.rooms[room][..self.depth]
+                .iter()
+                .all(|&space| space.map(|a| a.room()) == Some(room))
+        })
+    }
+
+    /// Least total energy needed to organize the amphipods, or `None` if
+    /// they can not be organized.
+    pub fn minimum_energy(&self) -> Option<Cost> {
+        dijkstra(*self, Burrow::is_organized, Burrow::moves)
+    }
+
+    /// True, if `room` contains no amphipods of other types, so amphipods
+    /// may enter it and never have to leave it again.
+    fn accepts(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth]
+            .iter()
+            .flatten()
+            .all(|amphipod| amphipod.room() == room)
+    }
+
+    /// True, if no amphipod is in the hallway between `from` and `to`,
+    /// including `to` but not `from`.
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    /// All states reachable with a single move, with the energy it takes.
+    ///
+    /// Amphipods move from the top of a side room to any space of the
+    /// hallway, except for the ones outside of rooms, or from the hallway
+    /// into their own side room, once no other types are left in there.
+    fn moves(&self) -> Vec<(Burrow, Cost)> {
+        // Moving into the final room is never worse than any other move, and
+        // takes the same energy whenever it happens.
+        for (space, amphipod) in self.hallway.iter().enumerate() {
+            let Some(amphipod) = *amphipod else {
+                continue;
+            };
+            let room = amphipod.room();
+            let door = door(room);
+            if !self.accepts(room) || !self.hallway_clear(space, door) {
+                continue;
+            }
+
+            let Some(depth) = self.rooms[room][..self.depth]
+                .iter()
+                .rposition(Option::is_none)
+            else {
+                continue;
+            };
+            let mut next = *self;
+            next.hallway[space] = None;
+            next.rooms[room][depth] = Some(amphipod);
+            let steps = space.abs_diff(door) + depth + 1;
+            return vec![(next, steps as Cost * amphipod.energy())];
+        }
+
+        let mut moves = vec![];
+        for room in 0..ROOMS {
+            if self.accepts(room) {
+                continue;
+            }
+            let Some(depth) = self.rooms[room][..self.depth]
+                .iter()
+                .position(Option::is_some)
+            else {
+                continue;
+            };
+            let amphipod = self.rooms[room][depth].expect("Found above");
+
+            let door = door(room);
+            for space in (0..HALLWAY).filter(|&space| !is_door(space)) {
+                if !self.hallway_clear(door, space) {
+                    continue;
+                }
+
+                let mut next = *self;
+                next.rooms[room][depth] = None;
+                next.hallway[space] = Some(amphipod);
+                let steps = door.abs_diff(space) + depth + 1;
+                moves.push((next, steps as Cost * amphipod.energy()));
+            }
+        }
+
+        moves
+    }
+}
+
+fn space_char(space: Option<Amphipod>) -> char {
+    space.map_or('.', Amphipod::to_char)
+}
+
+/// Renders the diagram of the burrow.
+impl fmt::Display for Burrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", "#".repeat(HALLWAY + 2))?;
+        let hallway: String = self.hallway.map(space_char).iter().collect();
+        writeln!(f, "#{}#", hallway)?;
+
+        for depth in 0..self.depth {
+            let spaces: Vec<String> = (0..ROOMS)
+                .map(|room| space_char(self.rooms[room][depth]).to_string())
+                .collect();
+            if depth == 0 {
+                writeln!(f, "###{}###", spaces.join("#"))?;
+            } else {
+                writeln!(f, "  #{}#", spaces.join("#"))?;
+            }
+        }
+
+        write!(f, "  {}", "#".repeat(2 * ROOMS + 1))
+    }
+}
+
+/// Parses a space of the diagram at zero-based `line` and `column`.
+fn parse_space(
+    line: usize,
+    column: usize,
+    char: Option<char>,
+) -> Result<Option<Amphipod>> {
+    match char {
+        Some('.') => Ok(None),
+        Some(char) => Amphipod::from_char(char).map(Some).ok_or_else(|| {
+            Error::at(
+                line,
+                column,
+                format!("Expected an amphipod A-D or ., got {:?}", char),
+            )
+        }),
+        None => Err(Error::at(line, column, "Expected a space of the burrow")),
+    }
+}
+
+impl FromStr for Burrow {
+    type Err = Error;
+
+    /// Parses the diagram of the hallway and the side rooms below it, with
+    /// `.` for empty spaces. Side rooms may be 1 to 4 spaces deep.
+    fn from_str(input: &str) -> Result<Burrow> {
+        let lines: Vec<Vec<char>> =
+            input.lines().map(|line| line.chars().collect()).collect();
+
+        let mut hallway = [None; HALLWAY];
+        for (idx, space) in hallway.iter_mut().enumerate() {
+            let char = lines.get(1).and_then(|line| line.get(idx + 1));
+            *space = parse_space(1, idx + 1, char.copied())?;
+        }
+
+        // Side rooms continue until the bottom wall
+        let mut rooms = [[None; MAX_DEPTH]; ROOMS];
+        let mut depth = 0;
+        while let Some(line) = lines.get(2 + depth) {
+            if line.get(door(0) + 1) == Some(&'#') {
+                break;
+            }
+            if depth == MAX_DEPTH {
+                return Err(Error::at(
+                    2 + depth,
+                    0,
+                    "Side rooms are deeper than 4 spaces",
+                ));
+            }
+
+            for (room, spaces) in rooms.iter_mut().enumerate() {
+                let column = door(room) + 1;
+                spaces[depth] =
+                    parse_space(2 + depth, column, line.get(column).copied())?;
+            }
+            depth += 1;
+        }
+        if depth == 0 {
+            return Err(Error::at(
+                2,
+                0,
+                "Expected side rooms below the hallway",
+            ));
+        }
+
+        for (room, spaces) in rooms.iter().enumerate() {
+            if let Some(depth) = spaces[..depth]
+                .windows(2)
+                .position(|pair| pair[0].is_some() && pair[1].is_none())
+            {
+                return Err(Error::at(
+                    2 + depth,
+                    door(room) + 1,
+                    "Amphipods can not stand above an empty space",
+                ));
+            }
+        }
+
+        let burrow = Burrow {
+            hallway,
+            rooms,
+            depth,
+        };
+
+        let amphipods: Vec<Amphipod> = burrow
+            .hallway
+            .iter()
+            .chain(burrow.rooms.iter().flat_map(|room| &room[..depth]))
+            .flatten()
+            .copied()
+            .collect();
+        for room in 0..ROOMS {
+            let count = amphipods.iter().filter(|a| a.room() == room).count();
+            if count != depth {
+                return Err(Error::parse(
+                    0,
+                    format!(
+                        "Expected {} amphipods of type {}, got {}",
+                        depth,
+                        (b'A' + room as u8) as char,
+                        count
+                    ),
+                ));
+            }
+        }
+
+        Ok(burrow)
+    }
+}
+
+impl Solution for Burrow {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        minimum_energy(self)
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let unfolded = self.unfold().ok_or_else(|| {
+            Error::invalid("Only side rooms 2 deep can be unfolded.")
+        })?;
+        minimum_energy(&unfolded).map(Some)
+    }
+}
+
+fn minimum_energy(burrow: &Burrow) -> Result<Answer> {
+    burrow
+        .minimum_energy()
+        .map(Answer::from)
+        .ok_or_else(|| Error::invalid("The amphipods can not be organized."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn parse_and_unfold() {
+        let burrow = Burrow::from_file("data/day23/test.txt").unwrap();
+        assert_eq!(
+            burrow.to_string(),
+            "#############\n\
+             #...........#\n\
+             ###B#C#B#D###\n  \
+             #A#D#C#A#\n  \
+             #########"
+        );
+
+        let unfolded = burrow.unfold().unwrap();
+        assert_eq!(
+            unfolded.to_string(),
+            "#############\n\
+             #...........#\n\
+             ###B#C#B#D###\n  \
+             #D#C#B#A#\n  \
+             #D#B#A#C#\n  \
+             #A#D#C#A#\n  \
+             #########"
+        );
+        assert_eq!(unfolded.to_string().parse::<Burrow>().unwrap(), unfolded);
+        assert_eq!(unfolded.unfold(), None);
+    }
+
+    #[test]
+    fn minimum_energy() {
+        let burrow: Burrow = "#############\n\
+                              #.....D.....#\n\
+                              ###.#B#C#A###\n  \
+                              #A#B#C#D#\n  \
+                              #########"
+            .parse()
+            .unwrap();
+        assert!(!burrow.is_organized());
+        // A steps aside to the right (1 + 1 steps), D enters its room (3 + 1
+        // steps) and A walks to its own room (7 + 1 steps)
+        assert_eq!(burrow.minimum_energy(), Some(4010));
+
+        let burrow = Burrow::from_file("data/day23/test.txt").unwrap();
+        assert_eq!(burrow.minimum_energy(), Some(12521));
+        assert_eq!(burrow.unfold().unwrap().minimum_energy(), Some(44169));
+
+        // D and A block each other's way to their side rooms
+        let burrow: Burrow = "#############\n\
+                              #.....D.A...#\n\
+                              ###.#B#C#.###\n  \
+                              #A#B#C#D#\n  \
+                              #########"
+            .parse()
+            .unwrap();
+        assert_eq!(burrow.minimum_energy(), None);
+        assert_eq!(
+            burrow.part_one().unwrap_err().to_string(),
+            "The amphipods can not be organized."
+        );
+
+        let unfolded = Burrow::from_file("data/day23/test.txt")
+            .unwrap()
+            .unfold()
+            .unwrap();
+        assert_eq!(
+            unfolded.part_two().unwrap_err().to_string(),
+            "Only side rooms 2 deep can be unfolded."
+        );
+    }
+
+    #[test]
+    fn reject_invalid_diagrams() {
+        assert_eq!(
+            "#############\n\
+             #...........#\n\
+             ###B#C#B#E###\n  \
+             #A#D#C#A#\n  \
+             #########"
+                .parse::<Burrow>()
+                .unwrap_err()
+                .to_string(),
+            "3:10: Expected an amphipod A-D or ., got 'E'"
+        );
+        assert!("#############\n\
+                 #...........#\n\
+                 ###B#C#B#D###\n  \
+                 #A#D#C#B#\n  \
+                 #########"
+            .parse::<Burrow>()
+            .is_err());
+        assert_eq!(
+            "#############\n\
+             #A..........#\n\
+             ###B#C#B#D###\n  \
+             #A#D#C#.#\n  \
+             #########"
+                .parse::<Burrow>()
+                .unwrap_err()
+                .to_string(),
+            "3:10: Amphipods can not stand above an empty space"
+        );
+    }
+}
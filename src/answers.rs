@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::Answer;
+
+/// Known-correct answers to one puzzle input, read from a manifest next to
+/// the input file, e.g. `data/day17/input.answers` for `input.txt`:
+///
+/// ```text
+/// part 1: 7381
+/// part 2: 3019
+/// ```
+///
+/// Multi-line answers start on the line after `part N:` and last until the
+/// next part or the end of the file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl ExpectedAnswers {
+    /// Path of the manifest with the answers to the puzzle input in `input`.
+    pub fn manifest_for<P>(input: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        input.as_ref().with_extension("answers")
+    }
+
+    /// True, if `answer` is the `expected` one.
+    pub fn matches(expected: &str, answer: &Answer) -> bool {
+        answer.to_string() == expected
+    }
+}
+
+impl FromStr for ExpectedAnswers {
+    type Err = Error;
+
+    fn from_str(manifest: &str) -> Result<ExpectedAnswers> {
+        let mut answers = ExpectedAnswers::default();
+        // Part, which the following lines of a multi-line answer belong to
+        let mut multi_line: Option<&mut Option<String>> = None;
+
+        for (idx, line) in manifest.lines().enumerate() {
+            let line = line.trim_end();
+            let Some(rest) = line.strip_prefix("part ") else {
+                match multi_line.as_mut() {
+                    Some(Some(text)) if !line.is_empty() => {
+                        if !text.is_empty() {
+                            text.push('\n');
+                        }
+                        text.push_str(line);
+                    }
+                    _ if line.is_empty() => (),
+                    _ => {
+                        return Err(Error::at(
+                            idx,
+                            0,
+                            format!(
+                                "Expected `part N: answer`, got {:?}",
+                                line
+                            ),
+                        ))
+                    }
+                }
+                continue;
+            };
+
+            let (part, answer) = rest.split_once(':').ok_or_else(|| {
+                Error::at(idx, 0, "Expected `:` after the part")
+            })?;
+            let slot = match part {
+                "1" => &mut answers.part_one,
+                "2" => &mut answers.part_two,
+                _ => {
+                    return Err(Error::at(
+                        idx,
+                        5,
+                        format!("Part has to be 1 or 2, got {:?}", part),
+                    ))
+                }
+            };
+            if slot.is_some() {
+                return Err(Error::at(
+                    idx,
+                    5,
+                    format!("Answer to part {} is given twice", part),
+                ));
+            }
+
+            let answer = answer.trim();
+            *slot = Some(answer.to_string());
+            multi_line = answer.is_empty().then_some(slot);
+        }
+
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest() {
+        let answers: ExpectedAnswers =
+            "part 1: 17\npart 2:\n#####\n#...#\n\n".parse().unwrap();
+        assert_eq!(answers.part_one.as_deref(), Some("17"));
+        assert_eq!(answers.part_two.as_deref(), Some("#####\n#...#"));
+        assert!(ExpectedAnswers::matches("17", &Answer::Unsigned(17)));
+        assert_eq!(
+            ExpectedAnswers::manifest_for("data/day1/input.txt"),
+            PathBuf::from("data/day1/input.answers")
+        );
+    }
+
+    #[test]
+    fn reject_invalid_manifest() {
+        assert_eq!(
+            "part 1: 1\npart 3: 2"
+                .parse::<ExpectedAnswers>()
+                .unwrap_err()
+                .to_string(),
+            "2:6: Part has to be 1 or 2, got \"3\""
+        );
+        assert!("part 1: 1\npart 1: 2".parse::<ExpectedAnswers>().is_err());
+        assert!("part 1: 1\n42".parse::<ExpectedAnswers>().is_err());
+    }
+}
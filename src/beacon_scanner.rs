@@ -0,0 +1,487 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Number of beacons two scanners have to share to be aligned.
+const OVERLAP: usize = 12;
+
+/// Position or offset in 3D.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+/// Rotation by multiples of 90 degrees around the axes, as a matrix of
+/// -1, 0 and 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rotation {
+    matrix: [[i32; 3]; 3],
+}
+
+/// Position and orientation of a scanner, relative to scanner 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    pub position: Vector,
+    pub rotation: Rotation,
+}
+
+/// Beacons detected by one scanner, relative to its position and
+/// orientation.
+#[derive(Debug)]
+pub struct Scanner {
+    beacons: Vec<Vector>,
+    /// Number of pairs of beacons with each squared distance, which does
+    /// not depend on the position and orientation of the scanner.
+    distances: HashMap<i32, usize>,
+}
+
+#[derive(Debug)]
+pub struct ScannerReport {
+    scanners: Vec<Scanner>,
+    /// Zero-based line of the header of each scanner.
+    headers: Vec<usize>,
+}
+
+/// All scanners aligned to scanner 0, and the beacons they detected.
+#[derive(Debug)]
+pub struct BeaconMap {
+    alignments: Vec<Alignment>,
+    beacons: HashSet<Vector>,
+}
+
+impl Vector {
+    pub fn new(x: i32, y: i32, z: i32) -> Vector {
+        Vector { x, y, z }
+    }
+
+    pub fn manhattan_distance(&self, other: Vector) -> i32 {
+        (self.x - other.x).abs()
+            + (self.y - other.y).abs()
+            + (self.z - other.z).abs()
+    }
+
+    fn squared_length(&self) -> i32 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    fn coordinates(&self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl FromStr for Vector {
+    type Err = Error;
+
+    /// Parses a position `x,y,z`.
+    fn from_str(line: &str) -> Result<Vector> {
+        let coordinates: Vec<&str> = line.split(',').collect();
+        let [x, y, z] = coordinates[..] else {
+            return Err(Error::parse(
+                0,
+                format!("Expected position x,y,z, got {:?}", line),
+            ));
+        };
+
+        let parse = |token: &str| {
+            parse_token(token.trim(), column_of(line, token.trim()))
+        };
+        Ok(Vector::new(parse(x)?, parse(y)?, parse(z)?))
+    }
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    pub fn new(matrix: [[i32; 3]; 3]) -> Rotation {
+        Rotation { matrix }
+    }
+
+    /// All 24 orientations a scanner may have: facing along any of the 6
+    /// directions of the axes, with any of 4 directions being up.
+    ///
+    /// They are the matrices, which map each axis to a different axis,
+    /// either positive or negative, and have a determinant of 1. The other
+    /// 24 matrices of this kind mirror instead of rotating.
+    pub fn all() -> Vec<Rotation> {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        let mut rotations = vec![];
+        for axes in PERMUTATIONS {
+            for signs in 0..8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, &axis) in axes.iter().enumerate() {
+                    matrix[row][axis] =
+                        if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+
+                let rotation = Rotation { matrix };
+                if rotation.determinant() == 1 {
+                    rotations.push(rotation);
+                }
+            }
+        }
+
+        rotations
+    }
+
+    pub fn matrix(&self) -> [[i32; 3]; 3] {
+        self.matrix
+    }
+
+    pub fn apply(&self, vector: Vector) -> Vector {
+        let [x, y, z] = self.matrix.map(|row| {
+            row.iter()
+                .zip(vector.coordinates())
+                .map(|(a, b)| a * b)
+                .sum()
+        });
+        Vector::new(x, y, z)
+    }
+
+    fn determinant(&self) -> i32 {
+        let m = self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+impl Alignment {
+    /// Position relative to scanner 0 of a `beacon` relative to the scanner.
+    pub fn transform(&self, beacon: Vector) -> Vector {
+        self.rotation.apply(beacon) + self.position
+    }
+}
+
+impl Scanner {
+    fn new(beacons: Vec<Vector>) -> Scanner {
+        let mut distances = HashMap::new();
+        for (idx, &a) in beacons.iter().enumerate() {
+            for &b in &beacons[idx + 1..] {
+                *distances.entry((a - b).squared_length()).or_insert(0) += 1;
+            }
+        }
+
+        Scanner { beacons, distances }
+    }
+
+    pub fn beacons(&self) -> &[Vector] {
+        &self.beacons
+    }
+
+    /// Two scanners with 12 common beacons share the distances of all 66
+    /// pairs of them. Scanners, which share fewer distances, can be skipped
+    /// without trying all rotations.
+    fn may_overlap(&self, other: &Scanner) -> bool {
+        let shared: usize = self
+            .distances
+            .iter()
+            .map(|(distance, &count)| {
+                count.min(other.distances.get(distance).copied().unwrap_or(0))
+            })
+            .sum();
+        shared >= OVERLAP * (OVERLAP - 1) / 2
+    }
+
+    /// Finds the alignment of this scanner, which makes at least 12 of its
+    /// beacons match `known` beacons relative to scanner 0.
+    fn align_to(&self, known: &[Vector]) -> Option<Alignment> {
+        for rotation in Rotation::all() {
+            let rotated: Vec<Vector> =
+                self.beacons.iter().map(|&b| rotation.apply(b)).collect();
+
+            // Count how many beacons match for each possible position
+            let mut matches: HashMap<Vector, usize> = HashMap::new();
+            for &a in known {
+                for &b in &rotated {
+                    let count = matches.entry(a - b).or_insert(0);
+                    *count += 1;
+                    if *count == OVERLAP {
+                        return Some(Alignment {
+                            position: a - b,
+                            rotation,
+                        });
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl ScannerReport {
+    pub fn scanners(&self) -> &[Scanner] {
+        &self.scanners
+    }
+
+    /// Aligns all scanners to scanner 0 by matching them with scanners,
+    /// which are already aligned.
+    ///
+    /// Fails for the first scanner, which can not be connected to scanner
+    /// 0 by a chain of scanners with 12 common beacons each.
+    pub fn align(&self) -> Result<BeaconMap> {
+        let count = self.scanners.len();
+        let mut alignments: Vec<Option<Alignment>> = vec![None; count];
+        // Beacons of aligned scanners, relative to scanner 0
+        let mut known: Vec<Vec<Vector>> = vec![vec![]; count];
+
+        alignments[0] = Some(Alignment {
+            position: Vector::default(),
+            rotation: Rotation::IDENTITY,
+        });
+        known[0] = self.scanners[0].beacons.clone();
+
+        // Each pair of scanners is tried at most once, when the first of
+        // them is aligned.
+        let mut queue = vec![0];
+        while let Some(aligned) = queue.pop() {
+            for other in 0..count {
+                if alignments[other].is_some()
+                    || !self.scanners[aligned]
+                        .may_overlap(&self.scanners[other])
+                {
+                    continue;
+                }
+
+                let scanner = &self.scanners[other];
+                if let Some(alignment) = scanner.align_to(&known[aligned]) {
+                    known[other] = scanner
+                        .beacons
+                        .iter()
+                        .map(|&b| alignment.transform(b))
+                        .collect();
+                    alignments[other] = Some(alignment);
+                    queue.push(other);
+                }
+            }
+        }
+
+        let alignments = alignments
+            .iter()
+            .enumerate()
+            .map(|(scanner, alignment)| {
+                alignment.ok_or_else(|| {
+                    Error::at(
+                        self.headers[scanner],
+                        0,
+                        format!(
+                            "Scanner {} shares no {} beacons with the others",
+                            scanner, OVERLAP
+                        ),
+                    )
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(BeaconMap {
+            alignments,
+            beacons: known.into_iter().flatten().collect(),
+        })
+    }
+}
+
+impl FromStr for ScannerReport {
+    type Err = Error;
+
+    /// Parses blocks of a header `--- scanner N ---`, followed by one
+    /// beacon `x,y,z` per line.
+    fn from_str(input: &str) -> Result<ScannerReport> {
+        let mut blocks: Vec<(usize, Vec<Vector>)> = vec![];
+
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(number) = line
+                .strip_prefix("--- scanner ")
+                .and_then(|line| line.strip_suffix(" ---"))
+            {
+                let number: usize =
+                    parse_token(number, column_of(line, number))
+                        .map_err(|e| e.offset_lines(idx))?;
+                if number != blocks.len() {
+                    return Err(Error::at(
+                        idx,
+                        0,
+                        format!("Expected scanner {}", blocks.len()),
+                    ));
+                }
+                blocks.push((idx, vec![]));
+            } else if let Some((_, beacons)) = blocks.last_mut() {
+                beacons.push(
+                    line.parse().map_err(|e: Error| e.offset_lines(idx))?,
+                );
+            } else {
+                return Err(Error::at(
+                    idx,
+                    0,
+                    "Expected a header --- scanner 0 ---",
+                ));
+            }
+        }
+
+        if blocks.is_empty() {
+            return Err(Error::parse(0, "There are no scanners"));
+        }
+
+        let (headers, scanners) = blocks
+            .into_iter()
+            .map(|(header, beacons)| (header, Scanner::new(beacons)))
+            .unzip();
+        Ok(ScannerReport { scanners, headers })
+    }
+}
+
+impl BeaconMap {
+    /// Position and orientation of each scanner, relative to scanner 0.
+    pub fn alignments(&self) -> &[Alignment] {
+        &self.alignments
+    }
+
+    /// All beacons relative to scanner 0, in no particular order.
+    pub fn beacons(&self) -> impl Iterator<Item = &Vector> {
+        self.beacons.iter()
+    }
+
+    pub fn beacon_count(&self) -> usize {
+        self.beacons.len()
+    }
+
+    /// Largest Manhattan distance between any two scanners.
+    pub fn largest_scanner_distance(&self) -> i32 {
+        let positions: Vec<Vector> =
+            self.alignments.iter().map(|a| a.position).collect();
+        positions
+            .iter()
+            .flat_map(|a| positions.iter().map(|&b| a.manhattan_distance(b)))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Solution for BeaconMap {
+    /// Parses the scanner report and aligns all scanners.
+    fn parse(input: &str) -> Result<Self> {
+        input.parse::<ScannerReport>()?.align()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.beacon_count().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.largest_scanner_distance().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        assert!(rotations.contains(&Rotation::IDENTITY));
+
+        let beacon = Vector::new(1, 2, 3);
+        let orientations: HashSet<Vector> =
+            rotations.iter().map(|r| r.apply(beacon)).collect();
+        assert_eq!(orientations.len(), 24);
+        assert!(orientations.contains(&Vector::new(-2, 1, 3)));
+        // Mirrored
+        assert!(!orientations.contains(&Vector::new(2, 1, 3)));
+    }
+
+    #[test]
+    fn align_scanners() {
+        let report = ScannerReport::from_file("data/day19/test.txt").unwrap();
+        let map = report.align().unwrap();
+
+        let positions: Vec<Vector> =
+            map.alignments().iter().map(|a| a.position).collect();
+        assert_eq!(
+            positions,
+            [
+                Vector::new(0, 0, 0),
+                Vector::new(68, -1246, -43),
+                Vector::new(1105, -1205, 1229),
+                Vector::new(-92, -2380, -20),
+                Vector::new(-20, -1133, 1061),
+            ]
+        );
+        assert_eq!(
+            map.alignments()[1]
+                .rotation
+                .apply(Vector::new(686, 422, 578))
+                + positions[1],
+            Vector::new(-618, -824, -621)
+        );
+
+        assert_eq!(map.beacon_count(), 79);
+        assert_eq!(map.largest_scanner_distance(), 3621);
+    }
+
+    #[test]
+    fn reject_invalid_reports() {
+        assert_eq!(
+            "--- scanner 0 ---\n1,2,3\n4,x,6"
+                .parse::<ScannerReport>()
+                .unwrap_err()
+                .to_string(),
+            "3:3: Could not parse number \"x\""
+        );
+        assert!("1,2,3".parse::<ScannerReport>().is_err());
+        assert!("--- scanner 1 ---\n1,2,3".parse::<ScannerReport>().is_err());
+
+        let report: ScannerReport =
+            "--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            report.align().unwrap_err().to_string(),
+            "4:1: Scanner 1 shares no 12 beacons with the others"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::cli::Part;
+use crate::json::Json;
+use aoc2021::{registry::Day, Result};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Fastest, median and slowest duration of one step over all runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+/// Timings of parsing the input of a day and solving its parts. Parts which
+/// were not selected or are not solved yet have no timing.
+pub struct Benchmark {
+    pub day: &'static Day,
+    pub parse: Timing,
+    pub part_one: Option<Timing>,
+    pub part_two: Option<Timing>,
+}
+
+impl Timing {
+    /// Summarizes the `durations` of all runs, of which there has to be at
+    /// least one.
+    fn from(mut durations: Vec<Duration>) -> Timing {
+        durations.sort_unstable();
+        Timing {
+            min: durations[0],
+            median: durations[durations.len() / 2],
+            max: durations[durations.len() - 1],
+        }
+    }
+
+    fn to_json(self) -> Json {
+        Json::object([
+            ("min_ns", Json::from(self.min)),
+            ("median_ns", Json::from(self.median)),
+            ("max_ns", Json::from(self.max)),
+        ])
+    }
+}
+
+/// Parses `input` and solves the selected `part` of `day` `runs` times.
+/// Errors if a part has no answer.
+pub fn run(
+    day: &'static Day,
+    input: &str,
+    runs: u32,
+    part: Option<Part>,
+) -> Result<Benchmark> {
+    let mut parse = vec![];
+    let mut part_one = vec![];
+    let mut part_two = vec![];
+    let mut part_two_solved = true;
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let solution = day.parse(black_box(input))?;
+        parse.push(start.elapsed());
+
+        if Part::One.is_selected(part) {
+            let start = Instant::now();
+            black_box(solution.part_one()?);
+            part_one.push(start.elapsed());
+        }
+
+        if Part::Two.is_selected(part) && part_two_solved {
+            let start = Instant::now();
+            part_two_solved = black_box(solution.part_two()?).is_some();
+            part_two.push(start.elapsed());
+        }
+    }
+
+    Ok(Benchmark {
+        day,
+        parse: Timing::from(parse),
+        part_one: (!part_one.is_empty()).then(|| Timing::from(part_one)),
+        part_two: (part_two_solved && !part_two.is_empty())
+            .then(|| Timing::from(part_two)),
+    })
+}
+
+/// Formats `duration` with a unit fitting its magnitude, e.g. `12.3ms`.
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{}ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.1}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.1}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+/// Prints a table with one row per day and step.
+pub fn print_table(benchmarks: &[Benchmark], runs: u32) {
+    println!("Timings over {} runs:", runs);
+    println!(
+        "{:>3}  {:<24}  {:<6}  {:>9}  {:>9}  {:>9}",
+        "Day", "Title", "Step", "Min", "Median", "Max"
+    );
+    for benchmark in benchmarks {
+        let steps = [
+            ("parse", Some(benchmark.parse)),
+            ("part 1", benchmark.part_one),
+            ("part 2", benchmark.part_two),
+        ];
+        for (step, timing) in steps {
+            let Some(timing) = timing else {
+                continue;
+            };
+            println!(
+                "{:>3}  {:<24}  {:<6}  {:>9}  {:>9}  {:>9}",
+                benchmark.day.number,
+                benchmark.day.title,
+                step,
+                format_duration(timing.min),
+                format_duration(timing.median),
+                format_duration(timing.max)
+            );
+        }
+    }
+}
+
+/// Summary of all `benchmarks` for comparing the timings of two commits.
+pub fn summary(benchmarks: &[Benchmark], runs: u32) -> Json {
+    let days = benchmarks
+        .iter()
+        .map(|benchmark| {
+            Json::object([
+                ("day", Json::from(benchmark.day.number)),
+                ("title", Json::from(benchmark.day.title)),
+                ("parse", benchmark.parse.to_json()),
+                ("part_one", benchmark.part_one.map(Timing::to_json).into()),
+                ("part_two", benchmark.part_two.map(Timing::to_json).into()),
+            ])
+        })
+        .collect();
+
+    Json::object([("runs", Json::from(runs)), ("days", Json::Array(days))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2021::registry;
+
+    #[test]
+    fn timing_statistics() {
+        let timing =
+            Timing::from([5, 1, 4, 2, 3].map(Duration::from_millis).to_vec());
+        assert_eq!(timing.min, Duration::from_millis(1));
+        assert_eq!(timing.median, Duration::from_millis(3));
+        assert_eq!(timing.max, Duration::from_millis(5));
+        assert_eq!(format_duration(timing.median), "3.0ms");
+    }
+
+    #[test]
+    fn benchmark_selected_parts() {
+        let day = registry::find(9).unwrap();
+        let input =
+            "2199943210\n3987894921\n9856789892\n8767896789\n9899965678";
+        let benchmark = run(day, input, 3, Some(Part::One)).unwrap();
+        assert!(benchmark.part_one.is_some());
+        assert!(benchmark.part_two.is_none());
+    }
+}
@@ -1,6 +1,9 @@
 use std::path::Path;
+use std::str::FromStr;
 
-use crate::file_handler::read_lines;
+use crate::error::{Error, Result};
+use crate::file_handler::FromInput;
+use crate::solution::{Answer, Solution};
 
 /// Tracks number of zeroes and ones over the column of a list of binary numbers.
 
@@ -17,6 +20,8 @@ struct Columns {
 pub struct DiagnosticsResult {
     gamma_rate: u32,
     epsilon_rate: u32,
+    oxygen_generator_rating: u32,
+    co2_scrubber_rating: u32,
 }
 
 impl Column {
@@ -33,13 +38,24 @@ impl Column {
         }
     }
 
-    fn most_common_digit(&self) -> &str {
+    /// Most common digit, `1` if both are equally common.
+    fn most_common_digit(&self) -> &'static str {
         if self.zeroes > self.ones {
             "0"
         } else {
             "1"
         }
     }
+
+    /// Least common digit, `0` if both are equally common. A digit, which
+    /// does not occur at all, is never the least common one.
+    fn least_common_digit(&self) -> &'static str {
+        if self.zeroes == 0 || (self.ones > 0 && self.ones < self.zeroes) {
+            "1"
+        } else {
+            "0"
+        }
+    }
 }
 
 impl Columns {
@@ -51,16 +67,33 @@ impl Columns {
         }
     }
 
-    pub fn update(&mut self, line: &str) {
-        assert!(self.columns.len() <= line.len(), "Line too short!");
-        if self.columns.len() < line.len() {
-            println!("Warning: Line is longer than available columns.")
+    /// Counts the digits of the binary number in `line`, which has to have
+    /// one digit per column.
+    pub fn update(&mut self, line: &str) -> Result<()> {
+        if line.len() != self.columns.len() {
+            return Err(Error::parse(
+                0,
+                format!(
+                    "Expected {} binary digits, got {:?}",
+                    self.columns.len(),
+                    line
+                ),
+            ));
         }
 
-        for (idx, column) in self.columns.iter_mut().enumerate() {
-            let digit = line.chars().collect::<Vec<_>>()[idx];
+        for (idx, (column, digit)) in
+            self.columns.iter_mut().zip(line.chars()).enumerate()
+        {
+            if digit != '0' && digit != '1' {
+                return Err(Error::parse(
+                    idx,
+                    format!("Expected binary digit, got {:?}", digit),
+                ));
+            }
             column.update(digit);
         }
+
+        Ok(())
     }
 
     pub fn most_common_digits(&self) -> String {
@@ -72,30 +105,43 @@ impl Columns {
 }
 
 impl DiagnosticsResult {
-    fn new(gamma_rate: u32, epsilon_rate: u32) -> DiagnosticsResult {
-        DiagnosticsResult {
-            gamma_rate,
-            epsilon_rate,
-        }
-    }
-
-    pub fn diagnose<P>(file: P) -> DiagnosticsResult
+    pub fn diagnose<P>(file: P) -> Result<DiagnosticsResult>
     where
         P: AsRef<Path>,
     {
-        let mut lines = read_lines(file).expect("Could not read input file.");
+        DiagnosticsResult::from_file(file)
+    }
 
-        // Initialize digit-counters for the columns with first line & process that line
-        let header = lines
+    pub fn power_consumption(&self) -> u32 {
+        self.gamma_rate * self.epsilon_rate
+    }
+
+    pub fn life_support_rating(&self) -> u32 {
+        self.oxygen_generator_rating * self.co2_scrubber_rating
+    }
+}
+
+impl FromStr for DiagnosticsResult {
+    type Err = Error;
+
+    /// Diagnoses a report with one binary number per line.
+    fn from_str(report: &str) -> Result<DiagnosticsResult> {
+        // Initialize digit-counters for the columns with first line
+        let header = report
+            .lines()
             .next()
-            .expect("File does not have a header (maybe empty?).")
-            .expect("Could not read header line.");
-        let mut columns = Columns::from(&header);
-        columns.update(&header);
-
-        // Process the rest of the file
-        for line in lines {
-            columns.update(&line.unwrap_or_default())
+            .ok_or_else(|| Error::parse(0, "Diagnostic report is empty."))?;
+        if header.is_empty() || header.len() > 32 {
+            return Err(Error::parse(
+                0,
+                "Expected binary numbers with 1 to 32 digits.",
+            ));
+        }
+        let mut columns = Columns::from(header);
+        let numbers: Vec<&str> = report.lines().collect();
+
+        for (idx, line) in numbers.iter().enumerate() {
+            columns.update(line).map_err(|e| e.offset_lines(idx))?;
         }
 
         // Build binary number out of most common digits
@@ -106,12 +152,63 @@ impl DiagnosticsResult {
             u32::from_str_radix(&flip_bits(&gamma_rate_binary), 2)
                 .expect("Could not parse binary gamma rate to decimal.");
 
-        DiagnosticsResult::new(gamma_rate, epsilon_rate)
+        Ok(DiagnosticsResult {
+            gamma_rate,
+            epsilon_rate,
+            oxygen_generator_rating: find_rating(
+                &numbers,
+                Column::most_common_digit,
+            )?,
+            co2_scrubber_rating: find_rating(
+                &numbers,
+                Column::least_common_digit,
+            )?,
+        })
+    }
+}
+
+impl Solution for DiagnosticsResult {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
     }
 
-    pub fn power_consumption(&self) -> u32 {
-        self.gamma_rate * self.epsilon_rate
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.power_consumption().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.life_support_rating().into()))
+    }
+}
+
+/// Filters the binary `numbers` column by column from the left, keeping the
+/// numbers with the digit `bit_criteria` selects from the counts of the
+/// remaining numbers, until only one number is left.
+/// The numbers have to be validated and of equal length.
+fn find_rating<F>(numbers: &[&str], bit_criteria: F) -> Result<u32>
+where
+    F: Fn(&Column) -> &'static str,
+{
+    let mut candidates = numbers.to_vec();
+    let width = candidates.first().map_or(0, |number| number.len());
+
+    let mut idx = 0;
+    while candidates.len() > 1 && idx < width {
+        let mut column = Column::new();
+        for number in &candidates {
+            column.update(number.as_bytes()[idx] as char);
+        }
+
+        let digit = bit_criteria(&column);
+        candidates.retain(|number| &number[idx..idx + 1] == digit);
+        idx += 1;
     }
+
+    let rating = candidates.first().ok_or_else(|| {
+        Error::parse(0, "No number matches the bit criteria.")
+    })?;
+    Ok(u32::from_str_radix(rating, 2)
+        .expect("Could not parse binary rating to decimal."))
 }
 
 /// Flips bits in given `binary_string`.
@@ -135,9 +232,55 @@ mod tests {
 
     #[test]
     fn run_diagnosis() {
-        let result = DiagnosticsResult::diagnose("data/day3/test.txt");
+        let result =
+            DiagnosticsResult::diagnose("data/day3/test.txt").unwrap();
         assert_eq!(result.gamma_rate, 22);
         assert_eq!(result.epsilon_rate, 9);
         assert_eq!(result.power_consumption(), 198);
     }
+
+    #[test]
+    fn life_support_rating() {
+        let result =
+            DiagnosticsResult::diagnose("data/day3/test.txt").unwrap();
+        assert_eq!(result.oxygen_generator_rating, 23);
+        assert_eq!(result.co2_scrubber_rating, 10);
+        assert_eq!(result.life_support_rating(), 230);
+    }
+
+    #[test]
+    fn rating_tie_breaking() {
+        // Equally many ones and zeroes keep the ones for the oxygen
+        // generator and the zeroes for the CO2 scrubber.
+        let numbers = ["10", "01"];
+        assert_eq!(
+            find_rating(&numbers, Column::most_common_digit).unwrap(),
+            2
+        );
+        assert_eq!(
+            find_rating(&numbers, Column::least_common_digit).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn rating_with_equal_digits() {
+        // All numbers start with 1, so the CO2 scrubber keeps them instead
+        // of filtering out every number.
+        let result = "110\n111".parse::<DiagnosticsResult>().unwrap();
+        assert_eq!(result.oxygen_generator_rating, 7);
+        assert_eq!(result.co2_scrubber_rating, 6);
+        assert!(find_rating(&[], Column::least_common_digit).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_digits() {
+        let mut columns = Columns::from("0101");
+        assert!(columns.update("0110").is_ok());
+        assert_eq!(
+            columns.update("01a0").unwrap_err().to_string(),
+            "1:3: Expected binary digit, got 'a'"
+        );
+        assert!(columns.update("011").is_err());
+    }
 }
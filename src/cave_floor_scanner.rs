@@ -1,95 +1,86 @@
-use crate::file_handler::read_lines;
-use std::path::Path;
+use crate::error::{Error, Result};
+use crate::grid::{Grid, Position};
+use crate::solution::{Answer, Solution};
+use std::collections::HashSet;
+use std::str::FromStr;
 
 type Height = u32;
+
+/// Locations of the heightmap, which flow down to the same low point. Basins
+/// are bounded by locations of height 9, which are in no basin.
+#[derive(Debug)]
+pub struct Basin {
+    pub low_point: Position,
+    /// All locations of the basin, starting with the low point.
+    pub cells: Vec<Position>,
+}
+
+#[derive(Debug)]
 pub struct CaveFloorScanner {
-    height_map: Vec<Vec<Height>>,
+    height_map: Grid<Height>,
 }
 
 impl CaveFloorScanner {
-    /// Creates a new cave floor scanner, loading the height-map at path `file`.
-    pub fn from_file<P>(file: P) -> CaveFloorScanner
-    where
-        P: AsRef<Path>,
-    {
-        let height_map: Vec<Vec<Height>> = read_lines(file)
-            .expect("Could not read heightmap-file")
-            .map(|line| {
-                line.expect("Could not read line in heightmap")
-                    .chars()
-                    .map(|character| {
-                        character.to_digit(10).unwrap_or_else(|| {
-                            panic!(
-                                "Could not parse character {} to integer.",
-                                character
-                            )
-                        })
-                    })
-                    .collect()
-            })
-            .collect();
-
-        CaveFloorScanner { height_map }
+    /// Returns height of point at `position` (row, col)
+    fn height(&self, position: Position) -> Height {
+        self.height_map[position]
     }
 
-    /// Returns height of point at coordinate (row, col)
-    fn height(&self, row: usize, col: usize) -> Height {
-        self.height_map[row][col]
+    /// Finds all lowest locations (== all surrounding locations are higher)
+    /// and returns their positions (row, col).
+    pub fn low_points(&self) -> Vec<Position> {
+        self.height_map
+            .positions()
+            .filter(|&position| {
+                self.height_map.neighbours(position).all(|neighbour| {
+                    self.height(neighbour) > self.height(position)
+                })
+            })
+            .collect()
     }
 
-    /// Gives all heights surrounding the given coordinate (row, col)
-    fn surrounding_heights(&self, row: usize, col: usize) -> Vec<Height> {
-        let mut heights: Vec<Height> = vec![];
-
-        // Up
-        if row > 0 {
-            heights.push(self.height(row - 1, col));
-        }
-
-        // Down
-        if row < self.height_map.len() - 1 {
-            heights.push(self.height(row + 1, col));
-        }
-
-        // Left
-        if col > 0 {
-            heights.push(self.height(row, col - 1));
-        }
-
-        // Right
-        if col < self.height_map[row].len() - 1 {
-            heights.push(self.height(row, col + 1));
-        }
-
-        heights
+    /// Finds all lowest locations and returns their heights.
+    fn find_heights_of_low_points(&self) -> Vec<Height> {
+        self.low_points()
+            .into_iter()
+            .map(|position| self.height(position))
+            .collect()
     }
 
-    /// Finds all lowest locations (== all surrounding locations are higher)
-    /// and returns their heights.
-    fn find_heights_of_low_points(&self) -> Vec<Height> {
-        // Stores (row, col)-coordinates of all low-points
-        let mut low_points: Vec<(usize, usize)> = vec![];
-
-        for row in 0..self.height_map.len() {
-            for col in 0..self.height_map[row].len() {
-                if self.height(row, col)
-                    < self
-                        .surrounding_heights(row, col)
-                        .into_iter()
-                        .min()
-                        .unwrap_or(u32::MAX)
-                {
-                    low_points.push((row, col));
+    /// Finds the basin around every low point by flood-filling from it
+    /// until locations of height 9.
+    pub fn basins(&self) -> Vec<Basin> {
+        self.low_points()
+            .into_iter()
+            .map(|low_point| {
+                let mut cells = vec![low_point];
+                let mut visited = HashSet::from([low_point]);
+                let mut next = 0;
+
+                while let Some(&position) = cells.get(next) {
+                    for neighbour in self.height_map.neighbours(position) {
+                        if self.height(neighbour) < 9
+                            && visited.insert(neighbour)
+                        {
+                            cells.push(neighbour);
+                        }
+                    }
+                    next += 1;
                 }
-            }
-        }
 
-        low_points
-            .into_iter()
-            .map(|(row, col)| self.height(row, col))
+                Basin { low_point, cells }
+            })
             .collect()
     }
 
+    /// Product of the sizes of the three largest basins.
+    pub fn largest_basins_product(&self) -> usize {
+        let mut sizes: Vec<usize> =
+            self.basins().iter().map(Basin::size).collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.iter().take(3).product()
+    }
+
     /// Returns the sum of the risk-levels of all low-points in the cave.
     pub fn cave_risk(&self) -> Height {
         self.find_heights_of_low_points()
@@ -99,13 +90,74 @@ impl CaveFloorScanner {
     }
 }
 
+impl Basin {
+    /// Number of locations in the basin.
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl FromStr for CaveFloorScanner {
+    type Err = Error;
+
+    /// Creates a new cave floor scanner from a height-map with one digit per
+    /// location.
+    fn from_str(heights: &str) -> Result<CaveFloorScanner> {
+        let height_map = Grid::parse_digits(heights)?;
+
+        Ok(CaveFloorScanner { height_map })
+    }
+}
+
+impl Solution for CaveFloorScanner {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.cave_risk().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.largest_basins_product().into()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_handler::FromInput;
 
     #[test]
     fn cave_risk() {
-        let scanner = CaveFloorScanner::from_file("data/day9/test.txt");
+        let scanner =
+            CaveFloorScanner::from_file("data/day9/test.txt").unwrap();
         assert_eq!(scanner.cave_risk(), 15);
     }
+
+    #[test]
+    fn find_basins() {
+        let scanner =
+            CaveFloorScanner::from_file("data/day9/test.txt").unwrap();
+        assert_eq!(scanner.low_points(), vec![(0, 1), (0, 9), (2, 2), (4, 6)]);
+
+        let basins = scanner.basins();
+        assert_eq!(
+            basins.iter().map(Basin::size).collect::<Vec<_>>(),
+            vec![3, 9, 14, 9]
+        );
+        assert_eq!(basins[0].low_point, (0, 1));
+        assert!(basins[0].cells.contains(&(1, 0)));
+        assert_eq!(scanner.largest_basins_product(), 1134);
+    }
+
+    #[test]
+    fn reject_non_digit_heights() {
+        let error =
+            CaveFloorScanner::from_file("data/day13/test.txt").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "data/day13/test.txt:1:2: Expected a digit, got ','"
+        );
+    }
 }
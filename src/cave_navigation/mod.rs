@@ -1,102 +1,175 @@
-mod pathfinding;
-use crate::file_handler::read_lines;
-use pathfinding::{shortest_path, Coordinate, Cost, Edge};
-use std::collections::HashMap;
+pub(crate) mod pathfinding;
+use crate::error::{Error, Result};
+use crate::file_handler::FromInput;
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+use pathfinding::dijkstra;
+pub use pathfinding::{Coordinate, Cost};
+use std::num::NonZeroUsize;
 use std::path::Path;
+use std::str::FromStr;
 
-pub struct Cave {
-    // Entry (p, [e]) is all edges leaving point p.
-    edges: HashMap<Coordinate, Vec<Edge>>,
+/// The full cave of part two is 5 × 5 tiles.
+const FULL_CAVE_TILES: NonZeroUsize = NonZeroUsize::new(5).unwrap();
 
-    max_x: usize,
-    max_y: usize,
+#[derive(Debug)]
+pub struct Cave {
+    /// Risk level of each position of the original tile.
+    risk_levels: Grid<Cost>,
+    /// The cave consists of `factor` × `factor` copies of the original
+    /// tile, see `Cave::tiled`.
+    factor: usize,
 }
 
 impl Cave {
-    pub fn from_grid_file<P>(file: P) -> Cave
+    pub fn from_grid_file<P>(file: P) -> Result<Cave>
     where
         P: AsRef<Path>,
     {
-        let lines = read_lines(file).expect("Error reading file.");
-        let mut nodes: HashMap<Coordinate, Cost> = HashMap::new();
-        let mut max_x = 0;
-        let mut max_y = 0;
-
-        for (line_idx, line) in lines.into_iter().enumerate() {
-            max_x = max_x.max(line_idx);
-
-            for (node_idx, char) in line
-                .expect("Error reading line")
-                .chars()
-                .into_iter()
-                .enumerate()
-            {
-                max_y = max_y.max(node_idx);
-                let value = char.to_digit(10).expect("Error parsing number.");
-                nodes.insert(Coordinate::from((line_idx, node_idx)), value);
-            }
-        }
+        Cave::from_file(file)
+    }
 
-        let mut edges: HashMap<Coordinate, Vec<Edge>> = HashMap::new();
-        for (p, _) in nodes.iter() {
-            let mut qs: Vec<Coordinate> = vec![];
-
-            if p.x > 0 {
-                qs.push(Coordinate::new(p.x - 1, p.y));
-            }
-
-            if p.y > 0 {
-                qs.push(Coordinate::new(p.x, p.y - 1));
-            }
-
-            if p.x < max_x {
-                qs.push(Coordinate::new(p.x + 1, p.y));
-            }
-
-            if p.y < max_y {
-                qs.push(Coordinate::new(p.x, p.y + 1));
-            }
-
-            let edges_p: Vec<Edge> = qs
-                .into_iter()
-                .map(|q| {
-                    Edge::new(
-                        *p,
-                        q,
-                        *nodes
-                            .get(&q)
-                            .expect("Error getting coordinate cost."),
-                    )
-                })
-                .collect();
-
-            edges.insert(*p, edges_p);
+    /// The full cave, which repeats the original tile `factor` times to the
+    /// right and downwards. Each tile to the right or downwards increases
+    /// the risk levels by 1, wrapping from 9 back to 1.
+    ///
+    /// Risk levels of the copies are computed when the search reaches them,
+    /// so the tiled cave takes no more memory than the original one.
+    pub fn tiled(&self, factor: NonZeroUsize) -> Cave {
+        Cave {
+            risk_levels: self.risk_levels.clone(),
+            factor: factor.get(),
         }
+    }
 
-        Cave {
-            edges,
-            max_x,
-            max_y,
+    pub fn width(&self) -> usize {
+        self.risk_levels.width() * self.factor
+    }
+
+    pub fn height(&self) -> usize {
+        self.risk_levels.height() * self.factor
+    }
+
+    /// Risk level of entering `position`, which must be inside the cave.
+    pub fn risk_level(&self, position: Coordinate) -> Cost {
+        let (height, width) =
+            (self.risk_levels.height(), self.risk_levels.width());
+        let risk = self.risk_levels[(position.x % height, position.y % width)];
+        let tiles = (position.x / height + position.y / width) as Cost;
+
+        if tiles == 0 {
+            risk
+        } else {
+            (risk + tiles - 1) % 9 + 1
         }
     }
 
-    pub fn lowest_total_risk(&self) -> u32 {
-        shortest_path(
-            &self.edges,
+    /// Positions next to `position`, each with the risk of entering it.
+    fn successors(
+        &self,
+        position: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, Cost)> + '_ {
+        let Coordinate { x, y } = position;
+        [
+            x.checked_sub(1).map(|x| (x, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            (x + 1 < self.height()).then_some((x + 1, y)),
+            (y + 1 < self.width()).then_some((x, y + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|p| (p.into(), self.risk_level(p.into())))
+    }
+
+    /// Lowest total risk of a path from the top left to the bottom right,
+    /// or `None` if the cave is empty.
+    pub fn lowest_total_risk(&self) -> Option<Cost> {
+        let goal = Coordinate::new(
+            self.height().checked_sub(1)?,
+            self.width().checked_sub(1)?,
+        );
+        dijkstra(
             Coordinate::new(0, 0),
-            Coordinate::new(self.max_x, self.max_y),
+            |&position| position == goal,
+            |&position| self.successors(position),
         )
-        .expect("Could not find path to goal.")
     }
 }
 
+impl FromStr for Cave {
+    type Err = Error;
+
+    /// Parses a grid with one risk level per position.
+    fn from_str(grid: &str) -> Result<Cave> {
+        let risk_levels = Grid::parse_digits(grid)?;
+
+        Ok(Cave {
+            risk_levels,
+            factor: 1,
+        })
+    }
+}
+
+impl Solution for Cave {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        lowest_total_risk(self)
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        lowest_total_risk(&self.tiled(FULL_CAVE_TILES)).map(Some)
+    }
+}
+
+fn lowest_total_risk(cave: &Cave) -> Result<Answer> {
+    cave.lowest_total_risk()
+        .map(Answer::from)
+        .ok_or_else(|| Error::invalid("There is no path through the cave."))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_lowest_total_risk() {
-        let cave = Cave::from_grid_file("data/day15/test.txt");
-        assert_eq!(cave.lowest_total_risk(), 40);
+        let cave = Cave::from_grid_file("data/day15/test.txt").unwrap();
+        assert_eq!(cave.lowest_total_risk(), Some(40));
+    }
+
+    #[test]
+    fn tiled_cave() {
+        let cave = Cave::from_grid_file("data/day15/test.txt")
+            .unwrap()
+            .tiled(FULL_CAVE_TILES);
+        assert_eq!((cave.width(), cave.height()), (50, 50));
+        assert_eq!(cave.risk_level(Coordinate::new(0, 0)), 1);
+        assert_eq!(cave.risk_level(Coordinate::new(0, 10)), 2);
+        assert_eq!(cave.risk_level(Coordinate::new(0, 24)), 9);
+        // Risk level 7 in the original tile, plus 3 tiles, wraps to 1
+        assert_eq!(cave.risk_level(Coordinate::new(0, 34)), 1);
+        assert_eq!(cave.risk_level(Coordinate::new(49, 49)), 9);
+        assert_eq!(cave.lowest_total_risk(), Some(315));
+    }
+
+    #[test]
+    fn single_tile() {
+        let cave = Cave::from_grid_file("data/day15/test.txt")
+            .unwrap()
+            .tiled(NonZeroUsize::MIN);
+        assert_eq!((cave.width(), cave.height()), (10, 10));
+        assert_eq!(cave.lowest_total_risk(), Some(40));
+    }
+
+    #[test]
+    fn reject_ragged_grid() {
+        assert!("123\n45\n678".parse::<Cave>().is_err());
+        assert_eq!(
+            "123\n4x6".parse::<Cave>().unwrap_err().to_string(),
+            "2:2: Expected a digit, got 'x'"
+        );
     }
 }
@@ -1,20 +1,15 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::collections::HashMap;
+use std::hash::Hash;
 
 pub type Cost = u32;
 
-#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 pub struct Coordinate {
     pub x: usize,
     pub y: usize,
 }
-pub struct Edge {
-    #[allow(dead_code)]
-    from: Coordinate,
-    to: Coordinate,
-    cost: Cost,
-}
 
 impl Coordinate {
     pub fn new(x: usize, y: usize) -> Coordinate {
@@ -28,71 +23,76 @@ impl From<(usize, usize)> for Coordinate {
     }
 }
 
-impl Edge {
-    pub fn new(from: Coordinate, to: Coordinate, cost: Cost) -> Edge {
-        Edge { from, to, cost }
-    }
-}
-
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
+/// Node of the frontier with the cost of reaching it.
+struct State<N> {
     cost: Cost,
-    position: Coordinate,
+    node: N,
 }
 
 // Turn max-heap to min-heap
-impl Ord for State {
+impl<N> Ord for State<N> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.x.cmp(&other.position.x))
+        other.cost.cmp(&self.cost)
     }
 }
 
-impl PartialOrd for State {
+impl<N> PartialOrd for State<N> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-// Djikstra
-pub fn shortest_path(
-    edges: &HashMap<Coordinate, Vec<Edge>>,
-    start: Coordinate,
-    goal: Coordinate,
-) -> Option<Cost> {
-    let mut dist: HashMap<Coordinate, Cost> =
-        edges.iter().map(|(&p, _)| (p, Cost::MAX)).collect();
+impl<N> PartialEq for State<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
 
+impl<N> Eq for State<N> {}
+
+/// Djikstra over a graph, which is only known through `successors`. It
+/// returns the nodes reachable from a node, each with the cost of moving
+/// there, so nodes are only generated once the search reaches them.
+///
+/// Returns the lowest cost of reaching any node for which `is_goal` holds.
+pub fn dijkstra<N, F, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    mut successors: F,
+) -> Option<Cost>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    let mut dist: HashMap<N, Cost> = HashMap::new();
     let mut heap = BinaryHeap::new();
 
     // We're at `start`, with a zero cost.
-    dist.insert(start, 0);
+    dist.insert(start.clone(), 0);
     heap.push(State {
         cost: 0,
-        position: start,
+        node: start,
     });
 
     // Check the frontier with lower cost nodes first (min-heap)
-    while let Some(State { cost, position }) = heap.pop() {
-        if position == goal {
+    while let Some(State { cost, node }) = heap.pop() {
+        if is_goal(&node) {
             return Some(cost);
         }
 
-        if cost > dist[&position] {
+        if dist.get(&node).is_some_and(|&best| cost > best) {
             continue;
         }
 
-        for edge in &edges[&position] {
-            let next = State {
-                cost: cost + edge.cost,
-                position: edge.to,
-            };
-
-            if next.cost < dist[&next.position] {
-                heap.push(next);
-                dist.insert(next.position, next.cost);
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            if dist.get(&next).is_none_or(|&best| next_cost < best) {
+                dist.insert(next.clone(), next_cost);
+                heap.push(State {
+                    cost: next_cost,
+                    node: next,
+                });
             }
         }
     }
@@ -0,0 +1,322 @@
+use std::fmt;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: aoc2021 [OPTIONS] [DAYS]
+
+Arguments:
+  [DAYS]  Days to run, e.g. `7`, `1-9` or `3,5,7`. Runs all days if omitted.
+
+Options:
+  -p, --part <PART>    Only run part 1 or part 2.
+  -i, --input <FILE>   Read the puzzle input from FILE instead of
+                       data/dayN/input.txt. Use `-` to read from stdin.
+                       Requires a single day to be selected.
+  -b, --bench <RUNS>   Time parsing and solving each day RUNS times and print
+                       min, median and max instead of the answers.
+      --bench-output <FILE>
+                       Where --bench writes its JSON summary
+                       [default: target/bench.json].
+      --json           Print all answers with their type and timings as one
+                       JSON document.
+      --verify         Check the answers against the known-correct ones in
+                       the `.answers` manifest next to each input file.
+  -h, --help           Print this help.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    /// `data/dayN/input.txt`
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+/// What to do with the selected days.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Print the answers.
+    Solve,
+    /// Time each day `runs` times and write a summary to `summary`.
+    Bench { runs: u32, summary: PathBuf },
+    /// Compare the answers with the expected ones.
+    Verify,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    /// Days to run. `None` runs every implemented day.
+    pub days: Option<Vec<u32>>,
+    /// Part to run. `None` runs both parts.
+    pub part: Option<Part>,
+    pub input: Input,
+    pub mode: Mode,
+    /// Print the answers as JSON instead of sentences.
+    pub json: bool,
+    pub help: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Part {
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    /// True, if this part should run when the user selected `selection`.
+    pub fn is_selected(&self, selection: Option<Part>) -> bool {
+        selection.is_none_or(|part| part == *self)
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl Options {
+    /// Parses the command-line `args`, excluding the program name.
+    pub fn parse<I>(args: I) -> Result<Options, CliError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options {
+            days: None,
+            part: None,
+            input: Input::Default,
+            mode: Mode::Solve,
+            json: false,
+            help: false,
+        };
+        let mut bench_runs = None;
+        let mut bench_summary = None;
+        let mut verify = false;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "-p" | "--part" => {
+                    let value = next_value(&mut args, &arg)?;
+                    options.part = Some(parse_part(&value)?);
+                }
+                "-i" | "--input" => {
+                    let value = next_value(&mut args, &arg)?;
+                    options.input = match value.as_str() {
+                        "-" => Input::Stdin,
+                        path => Input::File(PathBuf::from(path)),
+                    };
+                }
+                "-b" | "--bench" => {
+                    let value = next_value(&mut args, &arg)?;
+                    bench_runs = Some(parse_runs(&value)?);
+                }
+                "--verify" => verify = true,
+                "--json" => options.json = true,
+                "--bench-output" => {
+                    let value = next_value(&mut args, &arg)?;
+                    bench_summary = Some(PathBuf::from(value));
+                }
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    return Err(CliError(format!("Unknown option {}", flag)));
+                }
+                days => {
+                    if options.days.is_some() {
+                        return Err(CliError(format!(
+                            "Days were already given, unexpected argument {}",
+                            days
+                        )));
+                    }
+                    options.days = Some(parse_days(days)?);
+                }
+            }
+        }
+
+        if options.input != Input::Default
+            && options.days.as_ref().is_none_or(|days| days.len() != 1)
+        {
+            return Err(CliError(
+                "--input requires exactly one day to be selected".to_string(),
+            ));
+        }
+
+        options.mode = match (bench_runs, bench_summary) {
+            (Some(_), _) if verify => {
+                return Err(CliError(
+                    "--bench and --verify can not be combined".to_string(),
+                ))
+            }
+            (Some(runs), summary) => Mode::Bench {
+                runs,
+                summary: summary
+                    .unwrap_or_else(|| PathBuf::from("target/bench.json")),
+            },
+            (None, Some(_)) => {
+                return Err(CliError(
+                    "--bench-output requires --bench".to_string(),
+                ))
+            }
+            (None, None) if verify => Mode::Verify,
+            (None, None) => Mode::Solve,
+        };
+
+        if options.json && options.mode != Mode::Solve {
+            return Err(CliError(
+                "--json can not be combined with --bench or --verify"
+                    .to_string(),
+            ));
+        }
+
+        if options.mode == Mode::Verify && options.input == Input::Stdin {
+            return Err(CliError(
+                "--verify needs an input file with an answers manifest"
+                    .to_string(),
+            ));
+        }
+
+        Ok(options)
+    }
+}
+
+fn next_value<I>(args: &mut I, flag: &str) -> Result<String, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| CliError(format!("Missing value for {}", flag)))
+}
+
+fn parse_part(value: &str) -> Result<Part, CliError> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(CliError(format!("Part has to be 1 or 2, got {}", value))),
+    }
+}
+
+fn parse_runs(value: &str) -> Result<u32, CliError> {
+    match value.parse() {
+        Ok(runs) if runs > 0 => Ok(runs),
+        _ => Err(CliError(format!(
+            "Number of runs has to be a positive number, got {}",
+            value
+        ))),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u32, CliError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| CliError(format!("Could not parse day {}", value)))
+}
+
+/// Parses a day selection like `7`, `1-9` or `3,5,7` (or mixes like
+/// `1-3,7`) into a sorted list of days without duplicates.
+fn parse_days(value: &str) -> Result<Vec<u32>, CliError> {
+    let mut days: Vec<u32> = vec![];
+
+    for item in value.split(',') {
+        if let Some((first, last)) = item.split_once('-') {
+            let (first, last) = (parse_day(first)?, parse_day(last)?);
+            if first > last {
+                return Err(CliError(format!("Empty range of days {}", item)));
+            }
+            days.extend(first..=last);
+        } else {
+            days.push(parse_day(item)?);
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, CliError> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn no_arguments_runs_everything() {
+        let options = parse(&[]).unwrap();
+        assert_eq!(options.days, None);
+        assert_eq!(options.part, None);
+        assert_eq!(options.input, Input::Default);
+        assert_eq!(options.mode, Mode::Solve);
+    }
+
+    #[test]
+    fn day_selection() {
+        assert_eq!(parse(&["7"]).unwrap().days, Some(vec![7]));
+        assert_eq!(parse(&["3-5"]).unwrap().days, Some(vec![3, 4, 5]));
+        assert_eq!(parse(&["9,1-3,2"]).unwrap().days, Some(vec![1, 2, 3, 9]));
+        assert!(parse(&["5-3"]).is_err());
+        assert!(parse(&["five"]).is_err());
+    }
+
+    #[test]
+    fn part_and_input() {
+        let options = parse(&["17", "--part", "2", "-i", "-"]).unwrap();
+        assert_eq!(options.days, Some(vec![17]));
+        assert_eq!(options.part, Some(Part::Two));
+        assert_eq!(options.input, Input::Stdin);
+
+        let options = parse(&["-i", "other.txt", "4"]).unwrap();
+        assert_eq!(options.input, Input::File(PathBuf::from("other.txt")));
+        assert!(parse(&["4", "-p", "3"]).is_err());
+    }
+
+    #[test]
+    fn input_requires_single_day() {
+        assert!(parse(&["-i", "other.txt"]).is_err());
+        assert!(parse(&["1-2", "-i", "other.txt"]).is_err());
+    }
+
+    #[test]
+    fn bench_mode() {
+        assert_eq!(
+            parse(&["--bench", "10", "15"]).unwrap().mode,
+            Mode::Bench {
+                runs: 10,
+                summary: PathBuf::from("target/bench.json")
+            }
+        );
+        assert_eq!(
+            parse(&["-b", "3", "--bench-output", "old.json"])
+                .unwrap()
+                .mode,
+            Mode::Bench {
+                runs: 3,
+                summary: PathBuf::from("old.json")
+            }
+        );
+        assert!(parse(&["-b", "0"]).is_err());
+        assert!(parse(&["--bench-output", "old.json"]).is_err());
+        assert_eq!(parse(&["--verify"]).unwrap().mode, Mode::Verify);
+        assert!(parse(&["--verify", "-b", "3"]).is_err());
+        assert!(parse(&["--json"]).unwrap().json);
+        assert!(parse(&["--json", "--verify"]).is_err());
+    }
+}
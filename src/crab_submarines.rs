@@ -1,6 +1,7 @@
-use std::path::Path;
+use std::str::FromStr;
 
-use crate::file_handler::read_first_line;
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
 
 pub enum FuelBurnRate {
     Constant,
@@ -12,28 +13,6 @@ pub struct CrabSubmarineFleet {
 }
 
 impl CrabSubmarineFleet {
-    pub fn from_file<P>(file: P) -> CrabSubmarineFleet
-    where
-        P: AsRef<Path>,
-    {
-        let crab_positions_string = read_first_line(file);
-        let crab_positions: Vec<usize> = crab_positions_string
-            .split(',')
-            .map(|position| {
-                position.parse::<usize>().unwrap_or_else(|_| {
-                    panic!("Could not parse crab position {}!", position)
-                })
-            })
-            .collect();
-
-        let mut crabs = vec![0; *crab_positions.iter().max().expect("Could not determine max crab position. Maybe the list of positions is empty?")+1];
-        for position in crab_positions {
-            crabs[position] += 1;
-        }
-
-        CrabSubmarineFleet { crabs }
-    }
-
     fn fuel_cost_constant(&self, destination: usize) -> i32 {
         self.crabs
             .iter()
@@ -93,13 +72,58 @@ impl CrabSubmarineFleet {
     }
 }
 
+impl FromStr for CrabSubmarineFleet {
+    type Err = Error;
+
+    /// Creates a fleet from a comma-separated list of crab positions in the
+    /// first line of `input`.
+    fn from_str(input: &str) -> Result<CrabSubmarineFleet> {
+        let crab_positions_string = input.lines().next().unwrap_or_default();
+        let crab_positions: Vec<usize> = crab_positions_string
+            .split(',')
+            .map(|position| {
+                parse_token(
+                    position,
+                    column_of(crab_positions_string, position),
+                )
+            })
+            .collect::<Result<_>>()?;
+
+        // The list can't be empty, as split always yields one item.
+        let mut crabs = vec![0; crab_positions.iter().max().unwrap_or(&0) + 1];
+        for position in crab_positions {
+            crabs[position] += 1;
+        }
+
+        Ok(CrabSubmarineFleet { crabs })
+    }
+}
+
+impl Solution for CrabSubmarineFleet {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let (_, fuel) = self.ideal_position_and_fuel(FuelBurnRate::Constant);
+        Ok(fuel.into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let (_, fuel) = self.ideal_position_and_fuel(FuelBurnRate::Increasing);
+        Ok(Some(fuel.into()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_handler::FromInput;
 
     #[test]
     fn fuel_costs() {
-        let fleet = CrabSubmarineFleet::from_file("data/day7/test.txt");
+        let fleet =
+            CrabSubmarineFleet::from_file("data/day7/test.txt").unwrap();
         assert_eq!(fleet.fuel_cost_constant(2), 37);
         assert_eq!(fleet.fuel_cost_constant(1), 41);
         assert_eq!(fleet.fuel_cost_constant(3), 39);
@@ -110,7 +134,8 @@ mod tests {
 
     #[test]
     fn ideal_position_simple() {
-        let fleet = CrabSubmarineFleet::from_file("data/day7/test.txt");
+        let fleet =
+            CrabSubmarineFleet::from_file("data/day7/test.txt").unwrap();
         assert_eq!(
             fleet.ideal_position_and_fuel(FuelBurnRate::Constant),
             (2, 37)
@@ -119,7 +144,8 @@ mod tests {
 
     #[test]
     fn ideal_position_increasing() {
-        let fleet = CrabSubmarineFleet::from_file("data/day7/test.txt");
+        let fleet =
+            CrabSubmarineFleet::from_file("data/day7/test.txt").unwrap();
         assert_eq!(
             fleet.ideal_position_and_fuel(FuelBurnRate::Increasing),
             (5, 168)
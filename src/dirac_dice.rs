@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Each player rolls the die this many times per turn.
+const ROLLS_PER_TURN: u32 = 3;
+
+/// Parameters of a game of Dirac Dice, see `Rules::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    /// Number of spaces on the circular track, numbered from 1.
+    board_size: u32,
+    /// Score with which a player wins.
+    target_score: u32,
+    /// Faces of the die, numbered from 1.
+    die_faces: u32,
+}
+
+/// Outcome of a game with the deterministic die.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PracticeResult {
+    pub losing_score: u64,
+    pub rolls: u64,
+}
+
+/// Starting positions of both players.
+#[derive(Debug)]
+pub struct DiracDice {
+    start: [u32; 2],
+}
+
+/// State of a game with player `turn` to move.
+type GameState = ([u32; 2], [u64; 2], usize);
+
+impl Rules {
+    /// The practice game with the deterministic 100-sided die.
+    pub const PRACTICE: Rules = Rules {
+        board_size: 10,
+        target_score: 1000,
+        die_faces: 100,
+    };
+
+    /// The real game with the quantum 3-sided die.
+    pub const DIRAC: Rules = Rules {
+        board_size: 10,
+        target_score: 21,
+        die_faces: 3,
+    };
+
+    /// Rules for a board of `board_size` spaces and a die with `die_faces`
+    /// faces, where players win with `target_score`. Errors unless the
+    /// board has spaces and the die has faces.
+    pub fn new(
+        board_size: u32,
+        target_score: u32,
+        die_faces: u32,
+    ) -> Result<Rules> {
+        if board_size == 0 || die_faces == 0 {
+            return Err(Error::invalid(format!(
+                "Expected a board and a die of at least 1, got {} spaces \
+                 and {} faces",
+                board_size, die_faces
+            )));
+        }
+
+        Ok(Rules {
+            board_size,
+            target_score,
+            die_faces,
+        })
+    }
+
+    pub fn board_size(&self) -> u32 {
+        self.board_size
+    }
+
+    pub fn target_score(&self) -> u32 {
+        self.target_score
+    }
+
+    pub fn die_faces(&self) -> u32 {
+        self.die_faces
+    }
+
+    /// Position after moving `steps` spaces forward from `position`.
+    fn advance(&self, position: u32, steps: u64) -> u32 {
+        let position =
+            (u64::from(position) - 1 + steps) % u64::from(self.board_size) + 1;
+        position as u32
+    }
+
+    /// Number of ways to roll each total in one turn, as pairs of
+    /// `(total, ways)`.
+    fn turn_totals(&self) -> Vec<(u64, u64)> {
+        let mut totals: HashMap<u64, u64> = HashMap::from([(0, 1)]);
+        for _ in 0..ROLLS_PER_TURN {
+            let mut next = HashMap::new();
+            for (total, ways) in totals {
+                for face in 1..=u64::from(self.die_faces) {
+                    *next.entry(total + face).or_insert(0) += ways;
+                }
+            }
+            totals = next;
+        }
+
+        totals.into_iter().collect()
+    }
+}
+
+impl PracticeResult {
+    /// Score of the losing player times the number of rolls, or `None` if
+    /// it does not fit into `u64`.
+    pub fn answer(&self) -> Option<u64> {
+        self.losing_score.checked_mul(self.rolls)
+    }
+}
+
+impl DiracDice {
+    /// Checks that both players start on the board of `rules`.
+    pub fn check(&self, rules: &Rules) -> Result<()> {
+        for (player, &position) in self.start.iter().enumerate() {
+            if position > rules.board_size {
+                return Err(Error::invalid(format!(
+                    "Player {} starts on space {}, which is not on the board \
+                     of {} spaces",
+                    player + 1,
+                    position,
+                    rules.board_size
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Plays with a deterministic die, which rolls 1, 2, 3, and so on,
+    /// starting over at 1 after its last face.
+    pub fn practice_game(&self, rules: &Rules) -> Result<PracticeResult> {
+        self.check(rules)?;
+        let faces = u64::from(rules.die_faces);
+        let mut positions = self.start;
+        let mut scores = [0; 2];
+        let mut rolls: u64 = 0;
+
+        for player in [0, 1].into_iter().cycle() {
+            let steps: u64 = (0..u64::from(ROLLS_PER_TURN))
+                .map(|roll| (rolls + roll) % faces + 1)
+                .sum();
+            rolls += u64::from(ROLLS_PER_TURN);
+
+            positions[player] = rules.advance(positions[player], steps);
+            scores[player] += u64::from(positions[player]);
+            if scores[player] >= u64::from(rules.target_score) {
+                break;
+            }
+        }
+
+        Ok(PracticeResult {
+            losing_score: scores[0].min(scores[1]),
+            rolls,
+        })
+    }
+
+    /// Plays with the quantum die, where each roll splits the universe into
+    /// one copy for each face. Returns the number of universes each player
+    /// wins in.
+    ///
+    /// Universes which reach the same state on different paths are counted
+    /// only once, weighted by the number of paths.
+    pub fn count_wins(&self, rules: &Rules) -> Result<[u64; 2]> {
+        self.check(rules)?;
+        let totals = rules.turn_totals();
+        let mut memo = HashMap::new();
+        let state = (self.start, [0, 0], 0);
+        Ok(count_wins_from(rules, &totals, state, &mut memo))
+    }
+}
+
+/// Wins of each player in all universes splitting off from `state`.
+fn count_wins_from(
+    rules: &Rules,
+    totals: &[(u64, u64)],
+    state: GameState,
+    memo: &mut HashMap<GameState, [u64; 2]>,
+) -> [u64; 2] {
+    if let Some(&wins) = memo.get(&state) {
+        return wins;
+    }
+
+    let (positions, scores, turn) = state;
+    let mut wins = [0; 2];
+    for &(total, ways) in totals {
+        let mut positions = positions;
+        let mut scores = scores;
+        positions[turn] = rules.advance(positions[turn], total);
+        scores[turn] += u64::from(positions[turn]);
+
+        if scores[turn] >= u64::from(rules.target_score) {
+            wins[turn] += ways;
+        } else {
+            let next = (positions, scores, 1 - turn);
+            let [first, second] = count_wins_from(rules, totals, next, memo);
+            wins[0] += ways * first;
+            wins[1] += ways * second;
+        }
+    }
+
+    memo.insert(state, wins);
+    wins
+}
+
+impl FromStr for DiracDice {
+    type Err = Error;
+
+    /// Parses two lines `Player N starting position: P`.
+    fn from_str(input: &str) -> Result<DiracDice> {
+        let mut start = [0; 2];
+        let mut lines = input.lines();
+
+        for (idx, position) in start.iter_mut().enumerate() {
+            let prefix = format!("Player {} starting position:", idx + 1);
+            let line = lines.next().unwrap_or_default();
+            let token = line.strip_prefix(&prefix).map(str::trim).ok_or_else(
+                || Error::at(idx, 0, format!("Expected {:?}", prefix)),
+            )?;
+
+            *position = parse_token(token, column_of(line, token))
+                .map_err(|e| e.offset_lines(idx))?;
+            if *position == 0 {
+                return Err(Error::at(
+                    idx,
+                    column_of(line, token),
+                    "Positions start at 1",
+                ));
+            }
+        }
+
+        Ok(DiracDice { start })
+    }
+}
+
+impl Solution for DiracDice {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let answer =
+            self.practice_game(&Rules::PRACTICE)?.answer().ok_or_else(
+                || Error::invalid("The answer does not fit into 64 bits."),
+            )?;
+        Ok(answer.into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let wins = self.count_wins(&Rules::DIRAC)?;
+        Ok(Some(wins[0].max(wins[1]).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn practice_game() {
+        let game = DiracDice::from_file("data/day21/test.txt").unwrap();
+        let result = game.practice_game(&Rules::PRACTICE).unwrap();
+        assert_eq!(
+            result,
+            PracticeResult {
+                losing_score: 745,
+                rolls: 993
+            }
+        );
+        assert_eq!(result.answer(), Some(739785));
+    }
+
+    #[test]
+    fn count_wins() {
+        let game = DiracDice::from_file("data/day21/test.txt").unwrap();
+        assert_eq!(
+            game.count_wins(&Rules::DIRAC).unwrap(),
+            [444356092776315, 341960390180808]
+        );
+
+        // With a single face, the quantum die is deterministic
+        let rules = Rules::new(10, 21, 1).unwrap();
+        let practice = game.practice_game(&rules).unwrap();
+        let wins = game.count_wins(&rules).unwrap();
+        assert_eq!(wins.iter().sum::<u64>(), 1);
+        assert_eq!(wins[0] == 1, practice.rolls % 6 == 3);
+    }
+
+    #[test]
+    fn reject_invalid_positions() {
+        assert_eq!(
+            "Player 1 starting position: 4\nPlayer 2 starting position: x"
+                .parse::<DiracDice>()
+                .unwrap_err()
+                .to_string(),
+            "2:29: Could not parse number \"x\""
+        );
+        assert!("Player 2 starting position: 4"
+            .parse::<DiracDice>()
+            .is_err());
+        assert!(
+            "Player 1 starting position: 0\nPlayer 2 starting position: 1"
+                .parse::<DiracDice>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn reject_invalid_rules() {
+        assert_eq!(
+            Rules::new(0, 21, 3).unwrap_err().to_string(),
+            "Expected a board and a die of at least 1, got 0 spaces and 3 \
+             faces"
+        );
+        assert!(Rules::new(10, 21, 0).is_err());
+
+        // Player 2 starts on space 8
+        let game = DiracDice::from_file("data/day21/test.txt").unwrap();
+        let rules = Rules::new(7, 21, 3).unwrap();
+        assert_eq!(
+            game.count_wins(&rules).unwrap_err().to_string(),
+            "Player 2 starts on space 8, which is not on the board of 7 \
+             spaces"
+        );
+        assert!(game.practice_game(&rules).is_err());
+
+        let game = DiracDice::parse(
+            "Player 1 starting position: 11\nPlayer 2 starting position: 1",
+        )
+        .unwrap();
+        assert!(game.part_one().is_err());
+        assert!(game.part_two().is_err());
+    }
+
+    #[test]
+    fn large_rules() {
+        let game = DiracDice::from_file("data/day21/test.txt").unwrap();
+        let rules = Rules::new(u32::MAX, u32::MAX, u32::MAX).unwrap();
+        let result = game.practice_game(&rules).unwrap();
+        assert!(result.losing_score < u64::from(u32::MAX));
+        assert_eq!(
+            result.answer(),
+            result.losing_score.checked_mul(result.rolls)
+        );
+    }
+}
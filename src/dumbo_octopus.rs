@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+
+type Energy = u32;
+
+/// Energy levels of a grid of dumbo octopuses.
+#[derive(Clone, Debug)]
+pub struct OctopusGrid {
+    energy: Grid<Energy>,
+}
+
+/// State of the grid after one step of the simulation.
+#[derive(Debug)]
+pub struct Step {
+    /// Number of the step, starting with 1.
+    pub number: usize,
+    /// Number of octopuses, which flashed during the step.
+    pub flashes: usize,
+    pub octopuses: OctopusGrid,
+}
+
+/// Iterator over the endless steps of a simulation, see `OctopusGrid::steps`.
+pub struct Steps {
+    octopuses: OctopusGrid,
+    number: usize,
+}
+
+impl OctopusGrid {
+    /// Simulates one step and returns the number of octopuses which flashed.
+    ///
+    /// The energy of every octopus increases by 1. Octopuses with an energy
+    /// above 9 flash, which increases the energy of all 8 neighbours, which
+    /// may flash in turn. Each octopus flashes at most once per step and
+    /// its energy is reset to 0 afterwards.
+    pub fn step(&mut self) -> usize {
+        let mut flashing = vec![];
+        for position in self.energy.positions() {
+            self.energy[position] += 1;
+            if self.energy[position] == 10 {
+                flashing.push(position);
+            }
+        }
+
+        let mut flashes = 0;
+        while let Some(position) = flashing.pop() {
+            flashes += 1;
+            let neighbours: Vec<_> =
+                self.energy.neighbours_diagonal(position).collect();
+            for neighbour in neighbours {
+                self.energy[neighbour] += 1;
+                if self.energy[neighbour] == 10 {
+                    flashing.push(neighbour);
+                }
+            }
+        }
+
+        for energy in self.energy.iter_mut() {
+            if *energy > 9 {
+                *energy = 0;
+            }
+        }
+
+        flashes
+    }
+
+    /// Iterates over the steps of simulating a copy of the grid.
+    pub fn steps(&self) -> Steps {
+        Steps {
+            octopuses: self.clone(),
+            number: 0,
+        }
+    }
+
+    /// Total number of flashes during the first `steps` steps.
+    pub fn count_flashes(&self, steps: usize) -> usize {
+        self.steps().take(steps).map(|step| step.flashes).sum()
+    }
+
+    /// Number of the first step in which all octopuses flash, or `None` if
+    /// they never do.
+    ///
+    /// There are finitely many energy levels, so the grid eventually
+    /// repeats. If it does before all octopuses flash, they never will.
+    pub fn first_synchronized_step(&self) -> Option<usize> {
+        let size = self.energy.width() * self.energy.height();
+        let mut seen = HashSet::from([self.energy.clone()]);
+        for step in self.steps() {
+            if step.flashes == size {
+                return Some(step.number);
+            }
+            if !seen.insert(step.octopuses.energy) {
+                return None;
+            }
+        }
+
+        unreachable!("Steps never end")
+    }
+}
+
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let flashes = self.octopuses.step();
+        self.number += 1;
+
+        Some(Step {
+            number: self.number,
+            flashes,
+            octopuses: self.octopuses.clone(),
+        })
+    }
+}
+
+/// Renders the energy levels row by row.
+impl fmt::Display for OctopusGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.energy.fmt(f)
+    }
+}
+
+impl FromStr for OctopusGrid {
+    type Err = Error;
+
+    /// Parses a grid with one digit per octopus for its energy level.
+    fn from_str(input: &str) -> Result<OctopusGrid> {
+        Ok(OctopusGrid {
+            energy: Grid::parse_digits(input)?,
+        })
+    }
+}
+
+impl Solution for OctopusGrid {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.count_flashes(100).into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let step = self.first_synchronized_step().ok_or_else(|| {
+            Error::invalid("The octopuses never flash all at once.")
+        })?;
+        Ok(Some(step.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn inspect_steps() {
+        let octopuses: OctopusGrid =
+            "11111\n19991\n19191\n19991\n11111".parse().unwrap();
+        let steps: Vec<Step> = octopuses.steps().take(2).collect();
+
+        assert_eq!(steps[0].number, 1);
+        assert_eq!(steps[0].flashes, 9);
+        assert_eq!(
+            steps[0].octopuses.to_string(),
+            "34543\n40004\n50005\n40004\n34543"
+        );
+        assert_eq!(steps[1].flashes, 0);
+        assert_eq!(
+            steps[1].octopuses.to_string(),
+            "45654\n51115\n61116\n51115\n45654"
+        );
+    }
+
+    #[test]
+    fn count_flashes() {
+        let octopuses = OctopusGrid::from_file("data/day11/test.txt").unwrap();
+        assert_eq!(octopuses.count_flashes(10), 204);
+        assert_eq!(octopuses.count_flashes(100), 1656);
+        assert_eq!(octopuses.first_synchronized_step(), Some(195));
+    }
+
+    #[test]
+    fn never_synchronized() {
+        // The octopuses flash one after the other, 2 steps apart
+        let octopuses = OctopusGrid::parse("02").unwrap();
+        assert_eq!(octopuses.first_synchronized_step(), None);
+        assert_eq!(
+            octopuses.part_two().unwrap_err().to_string(),
+            "The octopuses never flash all at once."
+        );
+    }
+}
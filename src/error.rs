@@ -0,0 +1,206 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors while reading or parsing puzzle input.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io {
+        file: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// The input is malformed. `line` and `column` are 1-based.
+    Parse {
+        file: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed, but has no answer, or a computation was
+    /// given invalid parameters.
+    Invalid { message: String },
+}
+
+impl Error {
+    /// Creates a parse error at the zero-based `column` of the first line.
+    /// Parsers of multi-line input move it to the right line with
+    /// `offset_lines`.
+    pub fn parse<S>(column: usize, message: S) -> Error
+    where
+        S: Into<String>,
+    {
+        Error::Parse {
+            file: None,
+            line: 1,
+            column: column + 1,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error, which does not point at a position in the input.
+    pub fn invalid<S>(message: S) -> Error
+    where
+        S: Into<String>,
+    {
+        Error::Invalid {
+            message: message.into(),
+        }
+    }
+
+    /// Creates a parse error at zero-based `line` and `column`.
+    pub fn at<S>(line: usize, column: usize, message: S) -> Error
+    where
+        S: Into<String>,
+    {
+        Error::parse(column, message).offset_lines(line)
+    }
+
+    /// Moves a parse error `lines` lines down, e.g. from the position within
+    /// one line to its position in the whole input.
+    pub fn offset_lines(self, lines: usize) -> Error {
+        match self {
+            Error::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => Error::Parse {
+                file,
+                line: line + lines,
+                column,
+                message,
+            },
+            error => error,
+        }
+    }
+
+    /// Moves a parse error `columns` columns to the right, e.g. from the
+    /// position within a token to its position in the line.
+    pub fn offset_columns(self, columns: usize) -> Error {
+        match self {
+            Error::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => Error::Parse {
+                file,
+                line,
+                column: column + columns,
+                message,
+            },
+            error => error,
+        }
+    }
+
+    /// Attaches the path of the input `file` to the error.
+    pub fn in_file<P>(self, path: P) -> Error
+    where
+        P: AsRef<Path>,
+    {
+        let path = Some(path.as_ref().to_path_buf());
+        match self {
+            Error::Io { source, .. } => Error::Io { file: path, source },
+            Error::Parse {
+                line,
+                column,
+                message,
+                ..
+            } => Error::Parse {
+                file: path,
+                line,
+                column,
+                message,
+            },
+            error => error,
+        }
+    }
+}
+
+/// Parses `token`, which starts at zero-based `column` of its line.
+pub(crate) fn parse_token<T>(token: &str, column: usize) -> Result<T>
+where
+    T: FromStr,
+{
+    token.parse().map_err(|_| {
+        Error::parse(column, format!("Could not parse number {:?}", token))
+    })
+}
+
+/// Zero-based column at which `token` starts in `line`, counted in
+/// characters like all columns of errors. `token` has to be a slice of
+/// `line`.
+pub(crate) fn column_of(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count()
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io {
+                file: Some(file),
+                source,
+            } => write!(f, "Could not read {}: {}", file.display(), source),
+            Error::Io { file: None, source } => {
+                write!(f, "Could not read input: {}", source)
+            }
+            Error::Parse {
+                file,
+                line,
+                column,
+                message,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file.display())?;
+                }
+                write!(f, "{}:{}: {}", line, column, message)
+            }
+            Error::Invalid { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse { .. } | Error::Invalid { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { file: None, source }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_location() {
+        let line = "1,2,x";
+        let token = line.split(',').nth(2).unwrap();
+        let error = parse_token::<u32>(token, column_of(line, token))
+            .unwrap_err()
+            .offset_lines(3)
+            .in_file("data/input.txt");
+
+        assert_eq!(
+            error.to_string(),
+            "data/input.txt:4:5: Could not parse number \"x\""
+        );
+
+        // Columns count characters, not bytes
+        let line = "ä,ö,x";
+        let token = line.split(',').nth(2).unwrap();
+        assert_eq!(column_of(line, token), 4);
+    }
+}
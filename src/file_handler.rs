@@ -1,29 +1,37 @@
-use std::fs::{read_to_string, File};
-use std::io::{self, BufRead};
+use crate::error::{Error, Result};
+use std::fs::read_to_string;
+use std::io::Read;
 use std::path::Path;
+use std::str::FromStr;
 
-pub fn read_lines<P>(file: P) -> io::Result<io::Lines<io::BufReader<File>>>
+pub fn read_all<P>(file: P) -> Result<String>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(file)?;
-    Ok(io::BufReader::new(file).lines())
+    read_to_string(&file).map_err(|e| Error::from(e).in_file(&file))
 }
 
-pub fn read_first_line<P>(file: P) -> String
-where
-    P: AsRef<Path>,
-{
-    read_lines(file)
-        .expect("Could not read file containing age of the fishes.")
-        .next()
-        .expect("Could not read first line of the given file.")
-        .expect("Could not turn the first line of the file into a string.")
-}
+/// Constructors for everything that can be parsed from puzzle input text
+/// with `str::parse`.
+pub trait FromInput: FromStr<Err = Error> {
+    /// Reads all of `reader`, e.g. stdin, and parses it.
+    fn from_reader<R>(mut reader: R) -> Result<Self>
+    where
+        R: Read,
+    {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        input.parse()
+    }
 
-pub fn read_all<P>(file: P) -> std::io::Result<String>
-where
-    P: AsRef<Path>,
-{
-    read_to_string(file)
+    /// Reads and parses `file`. Errors are reported with the path of the
+    /// file.
+    fn from_file<P>(file: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        read_all(&file)?.parse().map_err(|e: Error| e.in_file(file))
+    }
 }
+
+impl<T> FromInput for T where T: FromStr<Err = Error> {}
@@ -0,0 +1,331 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+/// Position of a cell as (row, col).
+pub type Position = (usize, usize);
+
+/// Offsets of the 4 neighbours above, below, left and right of a cell.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Offsets of all 8 neighbours of a cell, including the diagonal ones.
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Dense 2D grid, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` x `height` cells, which all have `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid of `width` x `height` cells with the value `cell`
+    /// returns for each position.
+    pub fn from_fn<F>(width: usize, height: usize, mut cell: F) -> Grid<T>
+    where
+        F: FnMut(Position) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(&mut cell)
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid from non-empty `rows` of equal length. Errors point at
+    /// the first row with a different length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 {
+            return Err(Error::parse(0, "Grid is empty."));
+        }
+        if let Some(row) = rows.iter().position(|cells| cells.len() != width) {
+            return Err(Error::at(
+                row,
+                0,
+                format!("Expected {} cells in every row", width),
+            ));
+        }
+
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a grid with one character per cell, e.g. a map of digits.
+    /// `cell` converts a character or returns the message of the error at
+    /// its position.
+    pub fn parse_with<F>(input: &str, mut cell: F) -> Result<Grid<T>>
+    where
+        F: FnMut(char) -> std::result::Result<T, String>,
+    {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, char)| {
+                        cell(char)
+                            .map_err(|message| Error::at(row, col, message))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_>>()?;
+
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// True, if `position` is inside of the grid.
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// Returns the cell at `position`, or `None` if it is out of bounds.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|idx| &mut self.cells[idx])
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then_some(position.0 * self.width + position.1)
+    }
+
+    /// All positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Positions of the up to 4 cells above, below, left and right of
+    /// `position`.
+    pub fn neighbours(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        self.offset_positions(position, &ORTHOGONAL)
+    }
+
+    /// Positions of the up to 8 cells around `position`, including the
+    /// diagonal ones.
+    pub fn neighbours_diagonal(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        self.offset_positions(position, &ALL_AROUND)
+    }
+
+    fn offset_positions<'a>(
+        &'a self,
+        (row, col): Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + 'a {
+        offsets.iter().filter_map(move |&(row_offset, col_offset)| {
+            let neighbour = (
+                row.checked_add_signed(row_offset)?,
+                col.checked_add_signed(col_offset)?,
+            );
+            self.contains(neighbour).then_some(neighbour)
+        })
+    }
+
+    /// Cells of the row with index `row`.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// All rows from top to bottom, which are empty if the grid has no
+    /// columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    /// Cells of the column with index `col`, from top to bottom, or none if
+    /// `col` is out of bounds.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let height = if col < self.width { self.height } else { 0 };
+        (0..height).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Mirrors the grid at its diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, row)].clone()
+        })
+    }
+
+    /// Creates a grid of the same size with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u32> {
+    /// Parses a grid with one decimal digit per cell.
+    pub fn parse_digits(input: &str) -> Result<Grid<u32>> {
+        Grid::parse_with(input, |char| {
+            char.to_digit(10)
+                .ok_or_else(|| format!("Expected a digit, got {:?}", char))
+        })
+    }
+}
+
+/// Panics if `position` is out of bounds, use `Grid::get` to check.
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        let idx = self.index_of(position).unwrap_or_else(|| {
+            panic!("Position {:?} is outside of the grid", position)
+        });
+        &self.cells[idx]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let idx = self.index_of(position).unwrap_or_else(|| {
+            panic!("Position {:?} is outside of the grid", position)
+        });
+        &mut self.cells[idx]
+    }
+}
+
+/// Renders the grid row by row without separators between the cells.
+impl<T> fmt::Display for Grid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_access() {
+        let grid = Grid::parse_digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+
+        assert_eq!(
+            Grid::parse_digits("12\n3x").unwrap_err().to_string(),
+            "2:2: Expected a digit, got 'x'"
+        );
+        assert!(Grid::parse_digits("12\n3").is_err());
+        assert!(Grid::parse_digits("").is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours_diagonal((0, 2)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbours_diagonal((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn empty_dimensions() {
+        let grid = Grid::new(0, 3, 0);
+        assert!(grid.rows().all(|row| row.is_empty()));
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.column(0).count(), 0);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.to_string(), "\n\n");
+
+        let grid = Grid::from_fn(2, 0, |_| 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.column(1).count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Line of vents from `start` to `end`, both included. Lines are horizontal,
+/// vertical or diagonal at exactly 45 degrees.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VentLine {
+    start: Point,
+    end: Point,
+}
+
+/// All lines of hydrothermal vents on the ocean floor.
+#[derive(Debug)]
+pub struct HydrothermalVents {
+    lines: Vec<VentLine>,
+}
+
+impl VentLine {
+    pub fn is_diagonal(&self) -> bool {
+        self.start.x != self.end.x && self.start.y != self.end.y
+    }
+
+    /// All points covered by the line, from `start` to `end`.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let step_x = (self.end.x - self.start.x).signum();
+        let step_y = (self.end.y - self.start.y).signum();
+        let length = (self.end.x - self.start.x)
+            .abs()
+            .max((self.end.y - self.start.y).abs());
+        let start = self.start;
+
+        (0..=length).map(move |step| Point {
+            x: start.x + step * step_x,
+            y: start.y + step * step_y,
+        })
+    }
+}
+
+impl HydrothermalVents {
+    /// Number of points covered by at least two lines. Diagonal lines are
+    /// only taken into account with `include_diagonals`.
+    ///
+    /// Only covered points are counted in a map, so the number of points
+    /// does not depend on the range of the coordinates.
+    pub fn count_overlaps(&self, include_diagonals: bool) -> usize {
+        let mut covered: HashMap<Point, u32> = HashMap::new();
+
+        for line in &self.lines {
+            if line.is_diagonal() && !include_diagonals {
+                continue;
+            }
+
+            for point in line.points() {
+                *covered.entry(point).or_insert(0) += 1;
+            }
+        }
+
+        covered.values().filter(|&&lines| lines >= 2).count()
+    }
+}
+
+/// Parses a point `x,y`, which starts at zero-based `column` of its line.
+fn parse_point(point: &str, column: usize) -> Result<Point> {
+    let (x, y) = point.split_once(',').ok_or_else(|| {
+        Error::parse(column, format!("Expected point x,y, got {:?}", point))
+    })?;
+
+    Ok(Point {
+        x: parse_token(x.trim(), column)?,
+        y: parse_token(y.trim(), column + column_of(point, y))?,
+    })
+}
+
+impl FromStr for VentLine {
+    type Err = Error;
+
+    /// Parses a line `x1,y1 -> x2,y2`.
+    fn from_str(line: &str) -> Result<VentLine> {
+        let (start, end) = line.split_once("->").ok_or_else(|| {
+            Error::parse(0, format!("Expected x1,y1 -> x2,y2, got {:?}", line))
+        })?;
+        let vent_line = VentLine {
+            start: parse_point(start.trim(), 0)?,
+            end: parse_point(end.trim(), column_of(line, end.trim()))?,
+        };
+
+        let (width, height) = (
+            (vent_line.end.x - vent_line.start.x).abs(),
+            (vent_line.end.y - vent_line.start.y).abs(),
+        );
+        if vent_line.is_diagonal() && width != height {
+            return Err(Error::parse(
+                0,
+                "Line is neither horizontal, vertical nor at 45 degrees.",
+            ));
+        }
+
+        Ok(vent_line)
+    }
+}
+
+impl FromStr for HydrothermalVents {
+    type Err = Error;
+
+    /// Parses one line of vents per line of `input`.
+    fn from_str(input: &str) -> Result<HydrothermalVents> {
+        let lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                line.parse().map_err(|e: Error| e.offset_lines(idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(HydrothermalVents { lines })
+    }
+}
+
+impl Solution for HydrothermalVents {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.count_overlaps(false).into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.count_overlaps(true).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn count_overlaps() {
+        let vents =
+            HydrothermalVents::from_file("data/day5/test.txt").unwrap();
+        assert_eq!(vents.count_overlaps(false), 5);
+        assert_eq!(vents.count_overlaps(true), 12);
+    }
+
+    #[test]
+    fn diagonal_points() {
+        let line: VentLine = "9,7 -> 7,9".parse().unwrap();
+        assert_eq!(
+            line.points().map(|p| (p.x, p.y)).collect::<Vec<_>>(),
+            vec![(9, 7), (8, 8), (7, 9)]
+        );
+    }
+
+    #[test]
+    fn huge_coordinates() {
+        let vents: HydrothermalVents =
+            "0,2000000000 -> 0,2000000003\n-2,2000000001 -> 2,2000000001"
+                .parse()
+                .unwrap();
+        assert_eq!(vents.count_overlaps(false), 1);
+    }
+
+    #[test]
+    fn reject_invalid_lines() {
+        assert_eq!(
+            "0,9 -> 5,x".parse::<VentLine>().unwrap_err().to_string(),
+            "1:10: Could not parse number \"x\""
+        );
+        assert!("0,0 -> 1,2".parse::<VentLine>().is_err());
+        assert!("0,0 to 1,1".parse::<VentLine>().is_err());
+    }
+}
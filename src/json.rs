@@ -0,0 +1,172 @@
+use aoc2021::Answer;
+use std::fmt;
+use std::time::Duration;
+
+/// A JSON value, just enough to write the machine-readable output of the
+/// binary without pulling in a dependency.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Unsigned(u64),
+    Signed(i64),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys are written in the given order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Builds an object from `fields`.
+    pub fn object<I, K>(fields: I) -> Json
+    where
+        I: IntoIterator<Item = (K, Json)>,
+        K: Into<String>,
+    {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Writes the value with two spaces of indentation per level, starting
+    /// at `level`.
+    fn write_pretty(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        level: usize,
+    ) -> fmt::Result {
+        let indent = "  ".repeat(level + 1);
+        let closing_indent = "  ".repeat(level);
+        match self {
+            Json::Array(items) if !items.is_empty() => {
+                writeln!(f, "[")?;
+                for (idx, item) in items.iter().enumerate() {
+                    write!(f, "{}", indent)?;
+                    item.write_pretty(f, level + 1)?;
+                    writeln!(f, "{}", separator(idx, items.len()))?;
+                }
+                write!(f, "{}]", closing_indent)
+            }
+            Json::Object(fields) if !fields.is_empty() => {
+                writeln!(f, "{{")?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    write!(f, "{}", indent)?;
+                    write_string(f, key)?;
+                    write!(f, ": ")?;
+                    value.write_pretty(f, level + 1)?;
+                    writeln!(f, "{}", separator(idx, fields.len()))?;
+                }
+                write!(f, "{}}}", closing_indent)
+            }
+            Json::Array(_) => write!(f, "[]"),
+            Json::Object(_) => write!(f, "{{}}"),
+            Json::Null => write!(f, "null"),
+            Json::Unsigned(number) => write!(f, "{}", number),
+            Json::Signed(number) => write!(f, "{}", number),
+            Json::String(text) => write_string(f, text),
+        }
+    }
+}
+
+fn separator(idx: usize, len: usize) -> &'static str {
+    if idx + 1 < len {
+        ","
+    } else {
+        ""
+    }
+}
+
+/// Writes `text` as a quoted JSON string.
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for char in text.chars() {
+        match char {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            char if char.is_control() => write!(f, "\\u{:04x}", char as u32)?,
+            char => write!(f, "{}", char)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_pretty(f, 0)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(text: &str) -> Self {
+        Json::String(text.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(text: String) -> Self {
+        Json::String(text)
+    }
+}
+
+impl From<u32> for Json {
+    fn from(number: u32) -> Self {
+        Json::Unsigned(number.into())
+    }
+}
+
+impl From<u64> for Json {
+    fn from(number: u64) -> Self {
+        Json::Unsigned(number)
+    }
+}
+
+/// Durations are written in nanoseconds.
+impl From<Duration> for Json {
+    fn from(duration: Duration) -> Self {
+        Json::Unsigned(duration.as_nanos().try_into().unwrap_or(u64::MAX))
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Unsigned(number) => Json::Unsigned(*number),
+            Answer::Signed(number) => Json::Signed(*number),
+            Answer::Text(text) => Json::from(text.as_str()),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Json
+where
+    T: Into<Json>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_nested_values() {
+        let json = Json::object([
+            ("day", Json::from(13u32)),
+            ("answer", Json::from("#.\n\"x\"\\")),
+            ("parts", Json::Array(vec![Json::Signed(-1), Json::Null])),
+            ("empty", Json::Object(vec![])),
+        ]);
+        assert_eq!(
+            json.to_string(),
+            "{\n  \"day\": 13,\n  \"answer\": \"#.\\n\\\"x\\\"\\\\\",\n  \
+             \"parts\": [\n    -1,\n    null\n  ],\n  \"empty\": {}\n}"
+        );
+    }
+}
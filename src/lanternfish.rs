@@ -1,7 +1,9 @@
-use std::path::Path;
+use std::str::FromStr;
 
-use crate::file_handler::read_first_line;
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
 
+#[derive(Clone, Debug)]
 pub struct LanternfishColony {
     /// Buckets of fish
     /// fish[0]: number of fish with timer 0
@@ -15,27 +17,6 @@ impl LanternfishColony {
         LanternfishColony { fish: vec![0; 9] }
     }
 
-    pub fn from_file<P>(file: P) -> LanternfishColony
-    where
-        P: AsRef<Path>,
-    {
-        let mut colony = LanternfishColony::new();
-
-        let ages_string = read_first_line(file);
-
-        let ages = ages_string.split(',').map(|age| -> usize {
-            age.parse().unwrap_or_else(|_| {
-                panic!("Could not parse fish age to integer: {}", age)
-            })
-        });
-
-        for age in ages {
-            colony.fish[age] += 1;
-        }
-
-        colony
-    }
-
     /// Number of fish in the colony.
     pub fn size(&self) -> u64 {
         self.fish.iter().sum::<u64>()
@@ -65,13 +46,55 @@ impl LanternfishColony {
     }
 }
 
+impl FromStr for LanternfishColony {
+    type Err = Error;
+
+    /// Creates a colony from a comma-separated list of fish timers in the
+    /// first line of `input`.
+    fn from_str(input: &str) -> Result<LanternfishColony> {
+        let ages_string = input.lines().next().unwrap_or_default();
+        let mut colony = LanternfishColony::new();
+
+        for age_str in ages_string.split(',') {
+            let column = column_of(ages_string, age_str);
+            let age: usize = parse_token(age_str, column)?;
+            let bucket = colony.fish.get_mut(age).ok_or_else(|| {
+                Error::parse(column, format!("Fish timer {} is above 8", age))
+            })?;
+            *bucket += 1;
+        }
+
+        Ok(colony)
+    }
+}
+
+impl Solution for LanternfishColony {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let mut colony = self.clone();
+        colony.simulate_steps(80);
+        Ok(colony.size().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let mut colony = self.clone();
+        colony.simulate_steps(256);
+        Ok(Some(colony.size().into()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_handler::FromInput;
 
     #[test]
     fn simulate_test_colony() {
-        let mut colony = LanternfishColony::from_file("data/day6/test.txt");
+        let mut colony =
+            LanternfishColony::from_file("data/day6/test.txt").unwrap();
 
         colony.simulate_steps(18);
         assert_eq!(colony.size(), 26);
@@ -82,9 +105,22 @@ mod tests {
 
     #[test]
     fn simulate_test_colony_256days() {
-        let mut colony = LanternfishColony::from_file("data/day6/test.txt");
+        let mut colony =
+            LanternfishColony::from_file("data/day6/test.txt").unwrap();
 
         colony.simulate_steps(256);
         assert_eq!(colony.size(), 26984457539);
     }
+
+    #[test]
+    fn colony_from_reader() {
+        let colony = LanternfishColony::from_reader("3,4,3,1,2\n".as_bytes());
+        assert_eq!(colony.unwrap().size(), 5);
+    }
+
+    #[test]
+    fn reject_invalid_timer() {
+        let error = "3,4,9".parse::<LanternfishColony>().unwrap_err();
+        assert_eq!(error.to_string(), "1:5: Fish timer 9 is above 8");
+    }
 }
@@ -0,0 +1,74 @@
+//! Solutions for the Advent of Code 2021.
+//!
+//! Every day has its own module with a type holding the parsed puzzle input,
+//! which implements `Solution` and is re-exported here. All of them are
+//! listed in `registry::DAYS`.
+//!
+//! ```
+//! use aoc2021::LanternfishColony;
+//!
+//! let mut colony: LanternfishColony = "3,4,3,1,2".parse().unwrap();
+//! colony.simulate_steps(18);
+//! assert_eq!(colony.size(), 26);
+//! ```
+
+mod answers;
+mod error;
+mod file_handler;
+mod grid;
+pub mod registry;
+mod solution;
+
+pub mod amphipod;
+pub mod beacon_scanner;
+pub mod binary_diagnostic;
+pub mod cave_floor_scanner;
+pub mod cave_navigation;
+pub mod crab_submarines;
+pub mod dirac_dice;
+pub mod dumbo_octopus;
+pub mod hydrothermal_vents;
+pub mod lanternfish;
+pub mod packet_decoder;
+pub mod passage_pathing;
+pub mod polymerization;
+pub mod reactor_reboot;
+pub mod seven_segment;
+pub mod snailfish;
+pub mod sonar_depth;
+pub mod squid_bingo;
+pub mod submarine;
+pub mod syntax_scoring;
+pub mod transparent_origami;
+pub mod trench_exploration;
+pub mod trench_map;
+
+pub use answers::ExpectedAnswers;
+pub use error::{Error, Result};
+pub use file_handler::{read_all, FromInput};
+pub use grid::{Grid, Position};
+pub use solution::{Answer, Solution};
+
+pub use amphipod::Burrow;
+pub use beacon_scanner::BeaconMap;
+pub use binary_diagnostic::DiagnosticsResult;
+pub use cave_floor_scanner::CaveFloorScanner;
+pub use cave_navigation::Cave;
+pub use crab_submarines::CrabSubmarineFleet;
+pub use dirac_dice::DiracDice;
+pub use dumbo_octopus::OctopusGrid;
+pub use hydrothermal_vents::HydrothermalVents;
+pub use lanternfish::LanternfishColony;
+pub use packet_decoder::Packet;
+pub use passage_pathing::CaveSystem;
+pub use polymerization::Polymerizer;
+pub use reactor_reboot::RebootSteps;
+pub use seven_segment::SevenSegmentNotes;
+pub use snailfish::SnailfishHomework;
+pub use sonar_depth::SonarSweep;
+pub use squid_bingo::BingoGame;
+pub use submarine::Course;
+pub use syntax_scoring::NavigationSubsystem;
+pub use transparent_origami::TransparentPaper;
+pub use trench_exploration::ProbeLauncherSimulation;
+pub use trench_map::TrenchMap;
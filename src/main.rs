@@ -1,125 +1,194 @@
-mod file_handler;
-mod sonar_depth;
-mod submarine;
-use crate::{
-    crab_submarines::FuelBurnRate, submarine::Submarine,
-    transparent_origami::TransparentPaper,
+mod bench;
+mod cli;
+mod json;
+mod report;
+mod verify;
+use aoc2021::{
+    read_all,
+    registry::{self, Day, DAYS},
+    Error, Result,
 };
-mod binary_diagnostic;
-mod cave_floor_scanner;
-mod cave_navigation;
-mod crab_submarines;
-mod lanternfish;
-mod squid_bingo;
-mod transparent_origami;
-mod trench_exploration;
+use cli::{Input, Mode, Options, USAGE};
+use json::Json;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
 
 fn main() {
-    // Day 1
-    println!(
-        "Solution for day 1: {:?} increases, {:?} sliding window sum increases.",
-        sonar_depth::count_increases("data/day1/input.txt"),
-        sonar_depth::count_increases_sliding("data/day1/input.txt")
-    );
-
-    // Day 2
-    let mut submarine = submarine::SimpleSubmarine::new();
-    submarine.change_position_from("data/day2/input.txt");
-    print!(
-        "Solution for day 2: final horizontal position * final depth = {:?}. ",
-        submarine.position_hash()
-    );
-    let mut aimed_submarine = submarine::AimedSubmarine::new();
-    aimed_submarine.change_position_from("data/day2/input.txt");
-    println!("Aimed submarine: {:?}.", aimed_submarine.position_hash());
-
-    // Day 3
-    let diagnostics_result =
-        binary_diagnostic::DiagnosticsResult::diagnose("data/day3/input.txt");
-    print!(
-        "Solution for day 3: Power consumption = {:?}",
-        diagnostics_result.power_consumption()
-    );
-    println!();
-
-    // Day 4
-    let mut game = squid_bingo::BingoGame::build_from("data/day4/input.txt");
-    let result = game.simulate().unwrap();
-    print!(
-        "Solution for day 4: winning board's score * last number = {:?}. ",
-        result.answer()
-    );
-    let mut game2 = squid_bingo::BingoGame::build_from("data/day4/input.txt");
-    let result_end = game2
-        .simulate_until_end()
-        .unwrap_or_else(|| panic!("Game did not finish. {:#?}", game2));
-    println!(
-        "Last board to win, score * last number = {:?}!",
-        result_end.answer()
-    );
-
-    // Day 5
-
-    // Day 6
-    let mut colony =
-        lanternfish::LanternfishColony::from_file("data/day6/input.txt");
-    colony.simulate_steps(80);
-    print!(
-        "Solution for day 6: There are {} lanternfish after 80 days. ",
-        colony.size()
-    );
-    colony.simulate_steps(256 - 80);
-    println!(
-        "Part 2: There are {} lanternfish after 256 days.",
-        colony.size()
-    );
-
-    // Day 7
-    let fleet =
-        crab_submarines::CrabSubmarineFleet::from_file("data/day7/input.txt");
-    print!(
-        "Solution for day 7: Position / fuel cost: {:?}. ",
-        fleet.ideal_position_and_fuel(FuelBurnRate::Constant)
-    );
-    println!(
-        "Part 2, with increasing fuel-rate: {:?}. ",
-        fleet.ideal_position_and_fuel(FuelBurnRate::Increasing)
-    );
-
-    // Day 8
-
-    // Day 9
-    let scanner =
-        cave_floor_scanner::CaveFloorScanner::from_file("data/day9/input.txt");
-    print!("Solution for day 9: Cave risk is {}. ", scanner.cave_risk());
-    println!();
-
-    // Day 13
-    let mut paper = TransparentPaper::from_file("data/day13/input.txt");
-    paper.fold();
-    print!(
-        "Solution for day 13: Paper has {} markings after first fold.",
-        paper.num_marked()
-    );
-    println!();
-    println!("Final folded paper:");
-    while paper.fold() {}
-    paper.dump();
-
-    // Day 15
-    let cave = cave_navigation::Cave::from_grid_file("data/day15/input.txt");
-    print!("Lowest total risk is {}. ", cave.lowest_total_risk());
-    println!();
-
-    // Day 17
-    let sim = trench_exploration::ProbeLauncherSimulation::new(
-        185..=221,
-        -122..=-74,
-    );
-    let (highest_y, v_count) = sim.initial_velocity_for_highest_shot();
-    print!("Highest shot reaches height {}. ", highest_y);
-    println!(
-        "There are {} initial velocity values hitting the target area.",
-        v_count
-    );
+    let options = Options::parse(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(2);
+    });
+
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let days: Vec<&Day> = match &options.days {
+        None => DAYS.iter().collect(),
+        Some(numbers) => numbers
+            .iter()
+            .map(|&number| {
+                registry::find(number).unwrap_or_else(|| {
+                    eprintln!(
+                        "error: Day {} is not implemented. Available days: {:?}",
+                        number,
+                        DAYS.iter().map(|day| day.number).collect::<Vec<_>>()
+                    );
+                    process::exit(2);
+                })
+            })
+            .collect(),
+    };
+
+    if options.mode == Mode::Verify {
+        if !verify_days(&days, &options) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mut failed = false;
+    let mut benchmarks = vec![];
+    let mut results = vec![];
+    for day in days {
+        // Not every day has a personal puzzle input checked in. Those are
+        // only an error if the day was selected explicitly.
+        if options.days.is_none()
+            && options.input == Input::Default
+            && !day.default_input().exists()
+        {
+            eprintln!(
+                "note: Day {}: skipped, there is no puzzle input at {}",
+                day.number,
+                day.default_input().display()
+            );
+            continue;
+        }
+
+        let result =
+            read_input(day, &options.input).and_then(|(input, path)| {
+                match options.mode {
+                    Mode::Solve => report::solve(day, &input, options.part)
+                        .map(|result| {
+                            failed |= result.has_errors();
+                            if options.json {
+                                results.push(result.to_json());
+                            } else {
+                                result.print();
+                            }
+                        }),
+                    Mode::Bench { runs, .. } => {
+                        bench::run(day, &input, runs, options.part)
+                            .map(|benchmark| benchmarks.push(benchmark))
+                    }
+                    Mode::Verify => unreachable!("days are verified above"),
+                }
+                .map_err(|e| e.in_file(path))
+            });
+
+        if let Err(e) = result {
+            eprintln!("error: Day {}: {}", day.number, e);
+            if options.json {
+                results.push(report::error_json(day, &e));
+            }
+            failed = true;
+        }
+    }
+
+    if options.json {
+        println!("{}", Json::object([("days", Json::Array(results))]));
+    }
+
+    if let Mode::Bench { runs, summary } = &options.mode {
+        bench::print_table(&benchmarks, *runs);
+        let json = bench::summary(&benchmarks, *runs);
+        if let Err(e) = write_file(summary, &json.to_string()) {
+            eprintln!("error: Could not write {}: {}", summary.display(), e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Checks the answers of `days` against the manifests of their inputs and
+/// prints the outcome of every answer. Returns false on any regression.
+fn verify_days(days: &[&Day], options: &Options) -> bool {
+    let mut tally = verify::Tally::default();
+
+    for day in days {
+        let inputs = match &options.input {
+            Input::File(path) => Ok(vec![path.clone()]),
+            _ => verify::inputs_with_answers(day),
+        };
+        let inputs = inputs.unwrap_or_else(|e| {
+            eprintln!("error: Day {}: {}", day.number, e);
+            tally.failed += 1;
+            vec![]
+        });
+        if inputs.is_empty() {
+            println!("Day {}: no answers to verify", day.number);
+        }
+
+        for input in inputs {
+            match verify::check(day, &input, options.part) {
+                Ok(outcomes) => {
+                    for (part, outcome) in outcomes {
+                        println!(
+                            "Day {} {} part {}: {}",
+                            day.number,
+                            input.display(),
+                            part,
+                            outcome
+                        );
+                        tally.add(&outcome);
+                    }
+                }
+                Err(e) => {
+                    println!(
+                        "Day {} {}: FAIL, {}",
+                        day.number,
+                        input.display(),
+                        e
+                    );
+                    tally.failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("{}", tally);
+    !tally.has_regressions()
+}
+
+/// Reads the puzzle input of `day` and returns it with the path to report
+/// errors in it with.
+fn read_input(day: &Day, input: &Input) -> Result<(String, PathBuf)> {
+    match input {
+        Input::Default => {
+            let path = day.default_input();
+            Ok((read_all(&path)?, path))
+        }
+        Input::File(path) => Ok((read_all(path)?, path.clone())),
+        Input::Stdin => {
+            let path = PathBuf::from("<stdin>");
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| Error::from(e).in_file(&path))?;
+            Ok((input, path))
+        }
+    }
+}
+
+/// Writes `contents` to `path`, creating missing parent directories.
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", contents))
 }
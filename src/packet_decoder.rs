@@ -0,0 +1,559 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Type ID of literal value packets.
+const LITERAL: u64 = 4;
+
+/// Operation of an operator packet on the values of its sub-packets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    /// 1 if the first sub-packet is greater than the second, otherwise 0.
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+/// How the sub-packets of an operator packet are delimited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthType {
+    /// Length type 0: total length of the sub-packets in bits.
+    Bits,
+    /// Length type 1: number of sub-packets.
+    Packets,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Contents {
+    Literal(u64),
+    Operator {
+        operator: Operator,
+        length_type: LengthType,
+        packets: Vec<Packet>,
+    },
+}
+
+/// Packet of the Buoyancy Interchange Transmission System (BITS).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub contents: Contents,
+}
+
+/// Reads a transmission bit by bit.
+struct BitReader {
+    bits: Vec<bool>,
+    position: usize,
+}
+
+impl Operator {
+    fn from_type_id(type_id: u64) -> Option<Operator> {
+        match type_id {
+            0 => Some(Operator::Sum),
+            1 => Some(Operator::Product),
+            2 => Some(Operator::Minimum),
+            3 => Some(Operator::Maximum),
+            5 => Some(Operator::GreaterThan),
+            6 => Some(Operator::LessThan),
+            7 => Some(Operator::EqualTo),
+            _ => None,
+        }
+    }
+
+    fn type_id(&self) -> u64 {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Minimum => 2,
+            Operator::Maximum => 3,
+            Operator::GreaterThan => 5,
+            Operator::LessThan => 6,
+            Operator::EqualTo => 7,
+        }
+    }
+
+    /// Checks the number of sub-packets the operator is applied to.
+    fn accepts(&self, packets: usize) -> bool {
+        match self {
+            Operator::Sum | Operator::Product => true,
+            Operator::Minimum | Operator::Maximum => packets > 0,
+            _ => packets == 2,
+        }
+    }
+
+    /// Message for applying the operator to the wrong number of packets.
+    fn rejection(&self, packets: usize) -> String {
+        format!(
+            "Operator {} can not be applied to {} packets",
+            self, packets
+        )
+    }
+
+    /// Applies the operator to the values of its sub-packets. Errors if the
+    /// number of values is not accepted or the result does not fit into
+    /// `u64`.
+    fn apply(&self, values: &[u64]) -> Result<u64> {
+        let overflow = || {
+            Error::invalid(format!("The {} does not fit into 64 bits.", self))
+        };
+        let value = match (self, values) {
+            (Operator::Sum, _) => values
+                .iter()
+                .try_fold(0, |sum: u64, &value| sum.checked_add(value))
+                .ok_or_else(overflow)?,
+            (Operator::Product, _) => values
+                .iter()
+                .try_fold(1, |product: u64, &value| product.checked_mul(value))
+                .ok_or_else(overflow)?,
+            (Operator::Minimum, [_, ..]) => {
+                values.iter().copied().min().unwrap_or_default()
+            }
+            (Operator::Maximum, [_, ..]) => {
+                values.iter().copied().max().unwrap_or_default()
+            }
+            (Operator::GreaterThan, [first, second]) => {
+                (first > second).into()
+            }
+            (Operator::LessThan, [first, second]) => (first < second).into(),
+            (Operator::EqualTo, [first, second]) => (first == second).into(),
+            _ => return Err(Error::invalid(self.rejection(values.len()))),
+        };
+        Ok(value)
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "minimum",
+            Operator::Maximum => "maximum",
+            Operator::GreaterThan => "greater than",
+            Operator::LessThan => "less than",
+            Operator::EqualTo => "equal to",
+        };
+        f.write_str(name)
+    }
+}
+
+impl BitReader {
+    /// Column of the hexadecimal digit at the current position.
+    fn column(&self) -> usize {
+        self.position / 4
+    }
+
+    /// Reads the next `count` bits as a number.
+    fn read(&mut self, count: usize) -> Result<u64> {
+        let end = self.position + count;
+        let bits = self.bits.get(self.position..end).ok_or_else(|| {
+            Error::parse(
+                self.column(),
+                "Transmission ends in the middle of a packet",
+            )
+        })?;
+        self.position = end;
+
+        Ok(bits
+            .iter()
+            .fold(0, |value, &bit| value << 1 | u64::from(bit)))
+    }
+
+    fn read_packet(&mut self) -> Result<Packet> {
+        let version = self.read(3)? as u8;
+        let type_id = self.read(3)?;
+
+        let contents = match Operator::from_type_id(type_id) {
+            None => Contents::Literal(self.read_literal()?),
+            Some(operator) => {
+                let column = self.column();
+                let (length_type, packets) = self.read_sub_packets()?;
+                if !operator.accepts(packets.len()) {
+                    return Err(Error::parse(
+                        column,
+                        operator.rejection(packets.len()),
+                    ));
+                }
+                Contents::Operator {
+                    operator,
+                    length_type,
+                    packets,
+                }
+            }
+        };
+
+        Ok(Packet { version, contents })
+    }
+
+    /// Reads groups of 5 bits, each with 4 bits of the value. All groups
+    /// but the last start with 1.
+    fn read_literal(&mut self) -> Result<u64> {
+        let mut value: u64 = 0;
+        loop {
+            let column = self.column();
+            let group = self.read(5)?;
+            if value >> 60 != 0 {
+                return Err(Error::parse(
+                    column,
+                    "Literal value does not fit into 64 bits",
+                ));
+            }
+            value = value << 4 | group & 0b1111;
+
+            if group & 0b10000 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn read_sub_packets(&mut self) -> Result<(LengthType, Vec<Packet>)> {
+        let mut packets = vec![];
+
+        if self.read(1)? == 0 {
+            let length = self.read(15)? as usize;
+            let end = self.position + length;
+            while self.position < end {
+                packets.push(self.read_packet()?);
+            }
+            if self.position > end {
+                return Err(Error::parse(
+                    self.column(),
+                    "Sub-packets exceed their total length",
+                ));
+            }
+            Ok((LengthType::Bits, packets))
+        } else {
+            let count = self.read(11)?;
+            for _ in 0..count {
+                packets.push(self.read_packet()?);
+            }
+            Ok((LengthType::Packets, packets))
+        }
+    }
+}
+
+/// Appends the lowest `count` bits of `value`, most significant first.
+fn write(bits: &mut Vec<bool>, value: u64, count: usize) {
+    assert!(
+        count == 64 || value >> count == 0,
+        "{} exceeds {} bits",
+        value,
+        count
+    );
+    bits.extend((0..count).rev().map(|bit| value >> bit & 1 == 1));
+}
+
+impl Packet {
+    /// Sum of the versions of this packet and all nested sub-packets.
+    pub fn version_sum(&self) -> u64 {
+        let nested = match &self.contents {
+            Contents::Literal(_) => 0,
+            Contents::Operator { packets, .. } => {
+                packets.iter().map(Packet::version_sum).sum()
+            }
+        };
+        u64::from(self.version) + nested
+    }
+
+    /// Value of the expression the packet represents. Errors if an
+    /// operator is applied to the wrong number of packets, which the parser
+    /// rejects, or a value does not fit into `u64`.
+    pub fn evaluate(&self) -> Result<u64> {
+        match &self.contents {
+            Contents::Literal(value) => Ok(*value),
+            Contents::Operator {
+                operator, packets, ..
+            } => {
+                let values = packets
+                    .iter()
+                    .map(Packet::evaluate)
+                    .collect::<Result<Vec<u64>>>()?;
+                operator.apply(&values)
+            }
+        }
+    }
+
+    /// Encodes the packet as a transmission, padded with zeros to whole
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version does not fit into 3 bits or the sub-packets
+    /// into their length field.
+    pub fn to_hex(&self) -> String {
+        let mut bits = vec![];
+        self.write_bits(&mut bits);
+        bits.resize(bits.len().div_ceil(8) * 8, false);
+
+        bits.chunks(4)
+            .map(|digit| {
+                let value = digit
+                    .iter()
+                    .fold(0, |value, &bit| value << 1 | u32::from(bit));
+                char::from_digit(value, 16)
+                    .unwrap_or_default()
+                    .to_ascii_uppercase()
+            })
+            .collect()
+    }
+
+    fn write_bits(&self, bits: &mut Vec<bool>) {
+        write(bits, self.version.into(), 3);
+
+        match &self.contents {
+            Contents::Literal(value) => {
+                write(bits, LITERAL, 3);
+                let groups =
+                    (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+                for group in (0..groups).rev() {
+                    write(bits, (group > 0).into(), 1);
+                    write(bits, value >> (4 * group) & 0b1111, 4);
+                }
+            }
+            Contents::Operator {
+                operator,
+                length_type,
+                packets,
+            } => {
+                write(bits, operator.type_id(), 3);
+
+                let mut sub_packets = vec![];
+                for packet in packets {
+                    packet.write_bits(&mut sub_packets);
+                }
+                match length_type {
+                    LengthType::Bits => {
+                        write(bits, 0, 1);
+                        write(bits, sub_packets.len() as u64, 15);
+                    }
+                    LengthType::Packets => {
+                        write(bits, 1, 1);
+                        write(bits, packets.len() as u64, 11);
+                    }
+                }
+                bits.append(&mut sub_packets);
+            }
+        }
+    }
+
+    fn fmt_indented(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        depth: usize,
+    ) -> fmt::Result {
+        write!(f, "{:indent$}v{} ", "", self.version, indent = 2 * depth)?;
+
+        match &self.contents {
+            Contents::Literal(value) => write!(f, "literal {}", value),
+            Contents::Operator {
+                operator, packets, ..
+            } => {
+                write!(f, "{}", operator)?;
+                for packet in packets {
+                    writeln!(f)?;
+                    packet.fmt_indented(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Renders the packet tree with one packet per line, sub-packets indented
+/// below their operator.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl FromStr for Packet {
+    type Err = Error;
+
+    /// Decodes the outermost packet of a hexadecimal transmission. Bits
+    /// after it are padding and ignored.
+    fn from_str(hex: &str) -> Result<Packet> {
+        let mut bits = Vec::with_capacity(4 * hex.len());
+        for (column, char) in hex.trim_end().chars().enumerate() {
+            let digit = char.to_digit(16).ok_or_else(|| {
+                Error::parse(
+                    column,
+                    format!("Expected a hexadecimal digit, got {:?}", char),
+                )
+            })?;
+            bits.extend((0..4).rev().map(|bit| digit >> bit & 1 == 1));
+        }
+
+        BitReader { bits, position: 0 }.read_packet()
+    }
+}
+
+impl Solution for Packet {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.version_sum().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.evaluate()?.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    fn decode(hex: &str) -> Packet {
+        hex.parse().unwrap()
+    }
+
+    #[test]
+    fn decode_packets() {
+        assert_eq!(
+            decode("D2FE28"),
+            Packet {
+                version: 6,
+                contents: Contents::Literal(2021)
+            }
+        );
+
+        let packet = decode("38006F45291200");
+        assert_eq!(packet.version, 1);
+        assert_eq!(
+            packet.to_string(),
+            "v1 less than\n  v6 literal 10\n  v2 literal 20"
+        );
+
+        let packet = decode("EE00D40C823060");
+        match packet.contents {
+            Contents::Operator {
+                operator,
+                length_type,
+                packets,
+            } => {
+                assert_eq!(operator, Operator::Maximum);
+                assert_eq!(length_type, LengthType::Packets);
+                assert_eq!(packets.len(), 3);
+            }
+            Contents::Literal(_) => panic!("Expected an operator packet"),
+        }
+    }
+
+    #[test]
+    fn version_sums() {
+        assert_eq!(decode("8A004A801A8002F478").version_sum(), 16);
+        assert_eq!(decode("620080001611562C8802118E34").version_sum(), 12);
+        assert_eq!(decode("C0015000016115A2E0802F182340").version_sum(), 23);
+        assert_eq!(decode("A0016C880162017C3686B18A3D4780").version_sum(), 31);
+    }
+
+    #[test]
+    fn evaluate_expressions() {
+        assert_eq!(decode("C200B40A82").evaluate().unwrap(), 3);
+        assert_eq!(decode("04005AC33890").evaluate().unwrap(), 54);
+        assert_eq!(decode("880086C3E88112").evaluate().unwrap(), 7);
+        assert_eq!(decode("CE00C43D881120").evaluate().unwrap(), 9);
+        assert_eq!(decode("D8005AC2A8F0").evaluate().unwrap(), 1);
+        assert_eq!(decode("F600BC2D8F").evaluate().unwrap(), 0);
+        assert_eq!(decode("9C005AC2F8F0").evaluate().unwrap(), 0);
+
+        let packet = Packet::from_file("data/day16/test.txt").unwrap();
+        assert_eq!(packet.evaluate().unwrap(), 1);
+    }
+
+    #[test]
+    fn round_trip() {
+        for hex in [
+            "D2FE28",
+            "38006F45291200",
+            "EE00D40C823060",
+            "8A004A801A8002F478",
+            "620080001611562C8802118E34",
+            "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780",
+            "9C0141080250320F1802104A08",
+        ] {
+            assert_eq!(decode(hex).to_hex(), hex);
+        }
+
+        let packet = Packet {
+            version: 7,
+            contents: Contents::Operator {
+                operator: Operator::Product,
+                length_type: LengthType::Bits,
+                packets: vec![
+                    Packet {
+                        version: 0,
+                        contents: Contents::Literal(0),
+                    },
+                    Packet {
+                        version: 1,
+                        contents: Contents::Literal(u64::MAX),
+                    },
+                ],
+            },
+        };
+        assert_eq!(decode(&packet.to_hex()), packet);
+    }
+
+    #[test]
+    fn reject_invalid_transmissions() {
+        assert_eq!(
+            "D2FG28".parse::<Packet>().unwrap_err().to_string(),
+            "1:4: Expected a hexadecimal digit, got 'G'"
+        );
+        assert!("D2FE".parse::<Packet>().is_err());
+
+        let less_than_one = Packet {
+            version: 0,
+            contents: Contents::Operator {
+                operator: Operator::LessThan,
+                length_type: LengthType::Packets,
+                packets: vec![decode("D2FE28")],
+            },
+        };
+        assert!(less_than_one.to_hex().parse::<Packet>().is_err());
+        assert_eq!(
+            less_than_one.evaluate().unwrap_err().to_string(),
+            "Operator less than can not be applied to 1 packets"
+        );
+    }
+
+    #[test]
+    fn reject_overflow() {
+        let operation = |operator, values: [u64; 2]| Packet {
+            version: 0,
+            contents: Contents::Operator {
+                operator,
+                length_type: LengthType::Packets,
+                packets: values
+                    .map(|value| Packet {
+                        version: 0,
+                        contents: Contents::Literal(value),
+                    })
+                    .to_vec(),
+            },
+        };
+
+        let product = operation(Operator::Product, [1 << 32, 1 << 31]);
+        assert_eq!(product.evaluate().unwrap(), 1 << 63);
+        let product = operation(Operator::Product, [1 << 32, 1 << 32]);
+        assert_eq!(
+            product.evaluate().unwrap_err().to_string(),
+            "The product does not fit into 64 bits."
+        );
+
+        let sum = operation(Operator::Sum, [u64::MAX, 1]);
+        assert!(decode(&sum.to_hex()).evaluate().is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Undirected graph of caves connected by passages. Caves with an upper
+/// case name are large and may be visited any number of times.
+#[derive(Debug)]
+pub struct CaveSystem {
+    names: Vec<String>,
+    large: Vec<bool>,
+    /// `passages[c]` are the caves connected to cave `c`.
+    passages: Vec<Vec<usize>>,
+    start: usize,
+    end: usize,
+}
+
+/// Depth-first enumeration of all paths from `start` to `end`, see
+/// `CaveSystem::paths`.
+pub struct Paths<'a> {
+    caves: &'a CaveSystem,
+    /// A single small cave may be visited twice on each path.
+    allow_twice: bool,
+    /// Caves of the current path.
+    path: Vec<usize>,
+    /// Index of the next passage to try from each cave of the path.
+    next_passage: Vec<usize>,
+    /// Number of visits of each cave on the current path.
+    visits: Vec<u32>,
+    /// Position in the path of the small cave visited for the second time.
+    second_visit: Option<usize>,
+}
+
+impl CaveSystem {
+    fn is_small(&self, cave: usize) -> bool {
+        !self.large[cave]
+    }
+
+    /// Iterates over all paths from `start` to `end`, which visit small
+    /// caves at most once. With `allow_twice`, one small cave other than
+    /// `start` and `end` may be visited twice on each path.
+    pub fn paths(&self, allow_twice: bool) -> Paths<'_> {
+        let mut visits = vec![0; self.names.len()];
+        visits[self.start] = 1;
+
+        Paths {
+            caves: self,
+            allow_twice,
+            path: vec![self.start],
+            next_passage: vec![0],
+            visits,
+            second_visit: None,
+        }
+    }
+
+    /// Index of the cave `name`, which is added if it is new.
+    fn cave(&mut self, name: &str) -> usize {
+        if let Some(cave) = self.names.iter().position(|n| n == name) {
+            return cave;
+        }
+
+        self.names.push(name.to_string());
+        self.large
+            .push(name.chars().all(|c| c.is_ascii_uppercase()));
+        self.passages.push(vec![]);
+        self.names.len() - 1
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    /// Names of the caves of a path, from `start` to `end`.
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let caves = self.caves;
+
+        while let Some(&cave) = self.path.last() {
+            let depth = self.path.len() - 1;
+            let passage = self.next_passage[depth];
+
+            // Backtrack if the path is complete or all passages are tried
+            if cave == caves.end || passage >= caves.passages[cave].len() {
+                self.visits[cave] -= 1;
+                if self.second_visit == Some(depth) {
+                    self.second_visit = None;
+                }
+                self.path.pop();
+                self.next_passage.pop();
+                continue;
+            }
+
+            self.next_passage[depth] += 1;
+            let next = caves.passages[cave][passage];
+            if next == caves.start {
+                continue;
+            }
+            if caves.is_small(next) && self.visits[next] > 0 {
+                if !self.allow_twice || self.second_visit.is_some() {
+                    continue;
+                }
+                self.second_visit = Some(depth + 1);
+            }
+
+            self.visits[next] += 1;
+            self.path.push(next);
+            self.next_passage.push(0);
+
+            if next == caves.end {
+                return Some(
+                    self.path
+                        .iter()
+                        .map(|&cave| caves.names[cave].as_str())
+                        .collect(),
+                );
+            }
+        }
+
+        None
+    }
+}
+
+impl FromStr for CaveSystem {
+    type Err = Error;
+
+    /// Parses one passage `a-b` per line, connecting caves `a` and `b`.
+    fn from_str(input: &str) -> Result<CaveSystem> {
+        let mut caves = CaveSystem {
+            names: vec![],
+            large: vec![],
+            passages: vec![],
+            start: 0,
+            end: 0,
+        };
+
+        for (idx, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (a, b) = line.split_once('-').ok_or_else(|| {
+                Error::at(
+                    idx,
+                    0,
+                    format!("Expected passage a-b, got {:?}", line),
+                )
+            })?;
+            if a.is_empty() || b.is_empty() {
+                return Err(Error::at(idx, 0, "Cave names can not be empty"));
+            }
+
+            let (a, b) = (caves.cave(a), caves.cave(b));
+            if caves.large[a] && caves.large[b] {
+                return Err(Error::at(
+                    idx,
+                    0,
+                    "Passage between two large caves allows endless paths",
+                ));
+            }
+            caves.passages[a].push(b);
+            caves.passages[b].push(a);
+        }
+
+        let find = |name: &str| {
+            caves.names.iter().position(|n| n == name).ok_or_else(|| {
+                Error::at(
+                    input.lines().count(),
+                    0,
+                    format!("There is no cave {:?}", name),
+                )
+            })
+        };
+        let (start, end) = (find("start")?, find("end")?);
+        caves.start = start;
+        caves.end = end;
+
+        Ok(caves)
+    }
+}
+
+impl Solution for CaveSystem {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.paths(false).count().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.paths(true).count().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn list_paths() {
+        let caves = CaveSystem::from_file("data/day12/test.txt").unwrap();
+        let mut paths: Vec<String> =
+            caves.paths(false).map(|path| path.join(",")).collect();
+        paths.sort();
+
+        assert_eq!(paths.len(), 10);
+        assert_eq!(paths[0], "start,A,b,A,c,A,end");
+        assert!(paths.contains(&"start,b,end".to_string()));
+        assert_eq!(caves.paths(true).count(), 36);
+    }
+
+    #[test]
+    fn count_paths_larger_example() {
+        let caves =
+            CaveSystem::from_file("data/day12/test_large.txt").unwrap();
+        assert_eq!(caves.paths(false).count(), 226);
+        assert_eq!(caves.paths(true).count(), 3509);
+    }
+
+    #[test]
+    fn reject_invalid_passages() {
+        assert_eq!(
+            "start-A\nA end"
+                .parse::<CaveSystem>()
+                .unwrap_err()
+                .to_string(),
+            "2:1: Expected passage a-b, got \"A end\""
+        );
+        assert!("start-A\nA-B\nA-end".parse::<CaveSystem>().is_err());
+        assert!("start-A".parse::<CaveSystem>().is_err());
+    }
+}
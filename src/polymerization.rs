@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
+
+type Pair = (char, char);
+
+/// Polymer chain, which grows by inserting elements between pairs.
+///
+/// Instead of the chain itself, only the number of each pair of adjacent
+/// elements is stored. The memory needed does not grow with the number of
+/// steps, only with the number of different elements.
+#[derive(Clone, Debug)]
+pub struct Polymerizer {
+    /// Number of occurrences of each pair of adjacent elements in the chain.
+    pairs: HashMap<Pair, u128>,
+    /// Rules like `CH -> B`, which turn any pair CH into CB and BH.
+    rules: HashMap<Pair, char>,
+    /// The last element of the chain. It is the only one, which is not the
+    /// first element of a pair, and never changes.
+    last: char,
+}
+
+/// Adds `count` to the number of `key` in `counts`. Errors if the sum does
+/// not fit into `u128`, which happens after about 120 steps.
+fn add_count<K>(
+    counts: &mut HashMap<K, u128>,
+    key: K,
+    count: u128,
+) -> Result<()>
+where
+    K: Eq + Hash,
+{
+    let total = counts.entry(key).or_insert(0);
+    *total = total.checked_add(count).ok_or_else(|| {
+        Error::invalid("The polymer grows too long to count.")
+    })?;
+    Ok(())
+}
+
+impl Polymerizer {
+    /// Applies all rules to the chain once. Errors if the chain grows too
+    /// long to count its pairs.
+    pub fn apply_rules(&mut self) -> Result<()> {
+        let mut pairs = HashMap::with_capacity(self.pairs.len());
+
+        for (&(left, right), &count) in &self.pairs {
+            if let Some(&insertion) = self.rules.get(&(left, right)) {
+                add_count(&mut pairs, (left, insertion), count)?;
+                add_count(&mut pairs, (insertion, right), count)?;
+            } else {
+                add_count(&mut pairs, (left, right), count)?;
+            }
+        }
+
+        self.pairs = pairs;
+        Ok(())
+    }
+
+    pub fn apply_steps(&mut self, steps: usize) -> Result<()> {
+        for _ in 0..steps {
+            self.apply_rules()?;
+        }
+        Ok(())
+    }
+
+    /// Number of occurrences of each element in the chain.
+    pub fn frequencies(&self) -> Result<HashMap<char, u128>> {
+        let mut frequencies = HashMap::from([(self.last, 1)]);
+        for (&(left, _), &count) in &self.pairs {
+            add_count(&mut frequencies, left, count)?;
+        }
+
+        Ok(frequencies)
+    }
+
+    /// Number of the most common element minus number of the least common
+    /// element.
+    pub fn answer(&self) -> Result<u128> {
+        let frequencies = self.frequencies()?;
+        let max = frequencies.values().max().copied().unwrap_or(0);
+        let min = frequencies.values().min().copied().unwrap_or(0);
+        Ok(max - min)
+    }
+
+    /// Answer after applying the rules `steps` times to a copy of the chain.
+    fn answer_after(&self, steps: usize) -> Result<Answer> {
+        let mut polymerizer = self.clone();
+        polymerizer.apply_steps(steps)?;
+        let answer = u64::try_from(polymerizer.answer()?).map_err(|_| {
+            Error::invalid("The answer does not fit into 64 bits.")
+        })?;
+        Ok(answer.into())
+    }
+}
+
+impl FromStr for Polymerizer {
+    type Err = Error;
+
+    /// Parses the polymer template in the first line, followed by an empty
+    /// line and one insertion rule `AB -> C` per line.
+    fn from_str(input: &str) -> Result<Polymerizer> {
+        let mut lines = input.lines();
+        let template: Vec<char> =
+            lines.next().unwrap_or_default().chars().collect();
+        let last = *template
+            .last()
+            .ok_or_else(|| Error::parse(0, "Polymer template is empty."))?;
+
+        let mut pairs = HashMap::new();
+        for pair in template.windows(2) {
+            *pairs.entry((pair[0], pair[1])).or_insert(0) += 1;
+        }
+
+        let mut rules = HashMap::new();
+        for (idx, line) in lines.enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let rule =
+                line.split_once(" -> ").and_then(|(pair, insertion)| {
+                    let pair: Vec<char> = pair.chars().collect();
+                    let insertion: Vec<char> = insertion.chars().collect();
+                    match (pair.as_slice(), insertion.as_slice()) {
+                        (&[left, right], &[insertion]) => {
+                            Some(((left, right), insertion))
+                        }
+                        _ => None,
+                    }
+                });
+            let (pair, insertion) = rule.ok_or_else(|| {
+                Error::at(
+                    idx + 1,
+                    0,
+                    format!("Expected rule AB -> C, got {:?}", line),
+                )
+            })?;
+            rules.insert(pair, insertion);
+        }
+
+        Ok(Polymerizer { pairs, rules, last })
+    }
+}
+
+impl Solution for Polymerizer {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        self.answer_after(10)
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        self.answer_after(40).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn element_frequencies() {
+        let mut polymerizer =
+            Polymerizer::from_file("data/day14/test.txt").unwrap();
+        polymerizer.apply_rules().unwrap();
+        // NCNBCHB
+        assert_eq!(
+            polymerizer.frequencies().unwrap(),
+            HashMap::from([('N', 2), ('C', 2), ('B', 2), ('H', 1)])
+        );
+
+        polymerizer.apply_steps(9).unwrap();
+        let frequencies = polymerizer.frequencies().unwrap();
+        assert_eq!(frequencies[&'B'], 1749);
+        assert_eq!(frequencies[&'H'], 161);
+        assert_eq!(polymerizer.answer().unwrap(), 1588);
+    }
+
+    #[test]
+    fn forty_steps() {
+        let polymerizer =
+            Polymerizer::from_file("data/day14/test.txt").unwrap();
+        assert_eq!(
+            polymerizer.answer_after(40).unwrap(),
+            Answer::Unsigned(2188189693529)
+        );
+    }
+
+    #[test]
+    fn too_many_steps() {
+        let mut polymerizer =
+            Polymerizer::from_file("data/day14/test.txt").unwrap();
+        polymerizer.apply_steps(100).unwrap();
+        assert!(polymerizer.answer().is_ok());
+        assert!(polymerizer.answer_after(0).is_err());
+        assert_eq!(
+            polymerizer.apply_steps(100).unwrap_err().to_string(),
+            "The polymer grows too long to count."
+        );
+    }
+
+    #[test]
+    fn reject_invalid_rules() {
+        assert_eq!(
+            "NNCB\n\nCH -> B\nCHB"
+                .parse::<Polymerizer>()
+                .unwrap_err()
+                .to_string(),
+            "4:1: Expected rule AB -> C, got \"CHB\""
+        );
+    }
+}
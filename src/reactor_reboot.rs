@@ -0,0 +1,320 @@
+use std::str::FromStr;
+
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Cuboid of cubes from `min` to `max` along the x, y and z axes, both
+/// included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cuboid {
+    min: [i64; 3],
+    max: [i64; 3],
+}
+
+/// Step of the reboot, which turns all cubes of a cuboid on or off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RebootStep {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+#[derive(Debug)]
+pub struct RebootSteps {
+    steps: Vec<RebootStep>,
+}
+
+impl Cuboid {
+    /// The region of the initialization procedure, -50 to 50 on all axes.
+    pub const INITIALIZATION: Cuboid = Cuboid {
+        min: [-50; 3],
+        max: [50; 3],
+    };
+
+    /// Cuboid from `min` to `max`, or `None` if it is empty because `min`
+    /// exceeds `max` on any axis.
+    pub fn new(min: [i64; 3], max: [i64; 3]) -> Option<Cuboid> {
+        (0..3)
+            .all(|axis| min[axis] <= max[axis])
+            .then_some(Cuboid { min, max })
+    }
+
+    pub fn min(&self) -> [i64; 3] {
+        self.min
+    }
+
+    pub fn max(&self) -> [i64; 3] {
+        self.max
+    }
+
+    /// Number of cubes.
+    pub fn volume(&self) -> u64 {
+        (0..3)
+            .map(|axis| (self.max[axis] - self.min[axis] + 1) as u64)
+            .product()
+    }
+
+    /// Cubes in both cuboids, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        Cuboid::new(
+            [0, 1, 2].map(|axis| self.min[axis].max(other.min[axis])),
+            [0, 1, 2].map(|axis| self.max[axis].min(other.max[axis])),
+        )
+    }
+
+    /// Cubes of this cuboid, which are not in `other`, as at most 6
+    /// disjoint cuboids.
+    ///
+    /// The cuboid is cut along the faces of the intersection: first the
+    /// slabs before and after it along x, then of the remaining slab the
+    /// parts before and after it along y, and finally along z.
+    pub fn subtract(&self, other: &Cuboid) -> Vec<Cuboid> {
+        let Some(overlap) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for axis in 0..3 {
+            if rest.min[axis] < overlap.min[axis] {
+                let mut before = rest;
+                before.max[axis] = overlap.min[axis] - 1;
+                pieces.push(before);
+            }
+            if rest.max[axis] > overlap.max[axis] {
+                let mut after = rest;
+                after.min[axis] = overlap.max[axis] + 1;
+                pieces.push(after);
+            }
+            rest.min[axis] = overlap.min[axis];
+            rest.max[axis] = overlap.max[axis];
+        }
+
+        pieces
+    }
+}
+
+impl FromStr for Cuboid {
+    type Err = Error;
+
+    /// Parses ranges `x=a..b,y=c..d,z=e..f`.
+    fn from_str(ranges: &str) -> Result<Cuboid> {
+        let mut min = [0; 3];
+        let mut max = [0; 3];
+
+        let mut parts = ranges.split(',');
+        for (axis, name) in ["x=", "y=", "z="].iter().enumerate() {
+            let part = parts.next().ok_or_else(|| {
+                Error::parse(
+                    ranges.len(),
+                    format!("Expected range {}a..b", name),
+                )
+            })?;
+            let column = column_of(ranges, part);
+            let (from, to) = part
+                .strip_prefix(name)
+                .and_then(|range| range.split_once(".."))
+                .ok_or_else(|| {
+                    Error::parse(
+                        column,
+                        format!("Expected range {}a..b, got {:?}", name, part),
+                    )
+                })?;
+            min[axis] = parse_token(from, column_of(ranges, from))?;
+            max[axis] = parse_token(to, column_of(ranges, to))?;
+        }
+
+        if let Some(part) = parts.next() {
+            return Err(Error::parse(
+                column_of(ranges, part),
+                "Expected only ranges for x, y and z",
+            ));
+        }
+
+        Cuboid::new(min, max)
+            .ok_or_else(|| Error::parse(0, "Ranges must not be empty"))
+    }
+}
+
+impl FromStr for RebootStep {
+    type Err = Error;
+
+    /// Parses a step `on x=a..b,y=c..d,z=e..f`, or `off` instead of `on`.
+    fn from_str(line: &str) -> Result<RebootStep> {
+        let (state, ranges) = line.split_once(' ').ok_or_else(|| {
+            Error::parse(
+                0,
+                format!("Expected on/off and ranges, got {:?}", line),
+            )
+        })?;
+        let on = match state {
+            "on" => true,
+            "off" => false,
+            _ => {
+                return Err(Error::parse(
+                    0,
+                    format!("Expected on or off, got {:?}", state),
+                ))
+            }
+        };
+        let cuboid = ranges
+            .parse()
+            .map_err(|e: Error| e.offset_columns(column_of(line, ranges)))?;
+
+        Ok(RebootStep { on, cuboid })
+    }
+}
+
+impl RebootSteps {
+    /// Number of cubes, which are on after all steps. Only cubes inside of
+    /// `region` are taken into account, if given.
+    ///
+    /// The cubes which are on are kept as disjoint cuboids. Each step cuts
+    /// its cuboid out of all of them, and adds it afterwards if it turns
+    /// cubes on.
+    pub fn count_on(&self, region: Option<&Cuboid>) -> u64 {
+        let mut on: Vec<Cuboid> = vec![];
+
+        for step in &self.steps {
+            let cuboid = match region {
+                Some(region) => match step.cuboid.intersection(region) {
+                    Some(cuboid) => cuboid,
+                    None => continue,
+                },
+                None => step.cuboid,
+            };
+
+            on = on.iter().flat_map(|lit| lit.subtract(&cuboid)).collect();
+            if step.on {
+                on.push(cuboid);
+            }
+        }
+
+        on.iter().map(Cuboid::volume).sum()
+    }
+}
+
+impl FromStr for RebootSteps {
+    type Err = Error;
+
+    /// Parses one `RebootStep` per line.
+    fn from_str(input: &str) -> Result<RebootSteps> {
+        let steps = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                line.trim_end()
+                    .parse()
+                    .map_err(|e: Error| e.offset_lines(idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(RebootSteps { steps })
+    }
+}
+
+impl Solution for RebootSteps {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.count_on(Some(&Cuboid::INITIALIZATION)).into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.count_on(None).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    fn cuboid(ranges: &str) -> Cuboid {
+        ranges.parse().unwrap()
+    }
+
+    #[test]
+    fn cuboid_operations() {
+        let a = cuboid("x=0..2,y=0..2,z=0..2");
+        let b = cuboid("x=1..3,y=1..1,z=-5..5");
+
+        assert_eq!(a.volume(), 27);
+        assert_eq!(a.intersection(&b), Some(cuboid("x=1..2,y=1..1,z=0..2")));
+        assert_eq!(a.intersection(&cuboid("x=3..4,y=0..0,z=0..0")), None);
+
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(Cuboid::volume).sum::<u64>(), 27 - 6);
+        for (idx, piece) in pieces.iter().enumerate() {
+            assert_eq!(piece.intersection(&b), None);
+            for other in &pieces[idx + 1..] {
+                assert_eq!(piece.intersection(other), None);
+            }
+        }
+        assert!(a.subtract(&cuboid("x=-1..3,y=-1..3,z=-1..3")).is_empty());
+    }
+
+    #[test]
+    fn reboot() {
+        let steps = RebootSteps::from_file("data/day22/test.txt").unwrap();
+        assert_eq!(steps.count_on(None), 39);
+        assert_eq!(
+            steps.count_on(Some(&cuboid("x=10..11,y=10..11,z=10..11"))),
+            1
+        );
+    }
+
+    #[test]
+    fn compare_with_cube_by_cube() {
+        // Pseudo-random steps in a small region
+        let mut seed: u64 = 22;
+        let mut random = |range: i64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as i64 % range
+        };
+        let steps: Vec<RebootStep> = (0..20)
+            .map(|_| {
+                let min = [0; 3].map(|_| random(10) - 5);
+                let max = min.map(|min| min + random(6));
+                RebootStep {
+                    on: random(3) > 0,
+                    cuboid: Cuboid::new(min, max).unwrap(),
+                }
+            })
+            .collect();
+
+        let mut expected = 0;
+        for x in -5..10 {
+            for y in -5..10 {
+                for z in -5..10 {
+                    let cube = Cuboid::new([x, y, z], [x, y, z]).unwrap();
+                    let last = steps.iter().rev().find(|step| {
+                        step.cuboid.intersection(&cube).is_some()
+                    });
+                    if last.is_some_and(|step| step.on) {
+                        expected += 1;
+                    }
+                }
+            }
+        }
+
+        let steps = RebootSteps { steps };
+        assert_eq!(steps.count_on(None), expected);
+    }
+
+    #[test]
+    fn reject_invalid_steps() {
+        assert_eq!(
+            "on x=1..2,y=1..2,z=1..2\non x=1..2,y=a..2,z=1..2"
+                .parse::<RebootSteps>()
+                .unwrap_err()
+                .to_string(),
+            "2:13: Could not parse number \"a\""
+        );
+        assert!("toggle x=1..2,y=1..2,z=1..2".parse::<RebootStep>().is_err());
+        assert!("on x=1..2,y=1..2".parse::<RebootStep>().is_err());
+        assert!("on x=2..1,y=1..2,z=1..2".parse::<RebootStep>().is_err());
+    }
+}
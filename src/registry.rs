@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+
+use crate::amphipod::Burrow;
+use crate::beacon_scanner::BeaconMap;
+use crate::binary_diagnostic::DiagnosticsResult;
+use crate::cave_floor_scanner::CaveFloorScanner;
+use crate::cave_navigation::Cave;
+use crate::crab_submarines::CrabSubmarineFleet;
+use crate::dirac_dice::DiracDice;
+use crate::dumbo_octopus::OctopusGrid;
+use crate::error::Result;
+use crate::hydrothermal_vents::HydrothermalVents;
+use crate::lanternfish::LanternfishColony;
+use crate::packet_decoder::Packet;
+use crate::passage_pathing::CaveSystem;
+use crate::polymerization::Polymerizer;
+use crate::reactor_reboot::RebootSteps;
+use crate::seven_segment::SevenSegmentNotes;
+use crate::snailfish::SnailfishHomework;
+use crate::solution::Solution;
+use crate::sonar_depth::SonarSweep;
+use crate::squid_bingo::BingoGame;
+use crate::submarine::Course;
+use crate::syntax_scoring::NavigationSubsystem;
+use crate::transparent_origami::TransparentPaper;
+use crate::trench_exploration::ProbeLauncherSimulation;
+use crate::trench_map::TrenchMap;
+
+/// A day of the advent calendar with a solution in Rust.
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    parse: fn(&str) -> Result<Box<dyn Solution>>,
+}
+
+/// All days with a solution, ordered by day.
+pub static DAYS: [Day; 23] = [
+    Day::new::<SonarSweep>(1, "Sonar Sweep"),
+    Day::new::<Course>(2, "Dive!"),
+    Day::new::<DiagnosticsResult>(3, "Binary Diagnostic"),
+    Day::new::<BingoGame>(4, "Giant Squid"),
+    Day::new::<HydrothermalVents>(5, "Hydrothermal Venture"),
+    Day::new::<LanternfishColony>(6, "Lanternfish"),
+    Day::new::<CrabSubmarineFleet>(7, "The Treachery of Whales"),
+    Day::new::<SevenSegmentNotes>(8, "Seven Segment Search"),
+    Day::new::<CaveFloorScanner>(9, "Smoke Basin"),
+    Day::new::<NavigationSubsystem>(10, "Syntax Scoring"),
+    Day::new::<OctopusGrid>(11, "Dumbo Octopus"),
+    Day::new::<CaveSystem>(12, "Passage Pathing"),
+    Day::new::<TransparentPaper>(13, "Transparent Origami"),
+    Day::new::<Polymerizer>(14, "Extended Polymerization"),
+    Day::new::<Cave>(15, "Chiton"),
+    Day::new::<Packet>(16, "Packet Decoder"),
+    Day::new::<ProbeLauncherSimulation<i32>>(17, "Trick Shot"),
+    Day::new::<SnailfishHomework>(18, "Snailfish"),
+    Day::new::<BeaconMap>(19, "Beacon Scanner"),
+    Day::new::<TrenchMap>(20, "Trench Map"),
+    Day::new::<DiracDice>(21, "Dirac Dice"),
+    Day::new::<RebootSteps>(22, "Reactor Reboot"),
+    Day::new::<Burrow>(23, "Amphipod"),
+];
+
+impl Day {
+    const fn new<S>(number: u32, title: &'static str) -> Day
+    where
+        S: Solution + 'static,
+    {
+        Day {
+            number,
+            title,
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    /// Parses the puzzle `input` with the solution of this day.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>> {
+        (self.parse)(input)
+    }
+
+    /// Folder with the puzzle inputs of this day.
+    pub fn data_dir(&self) -> PathBuf {
+        PathBuf::from(format!("data/day{}", self.number))
+    }
+
+    /// Path of the personal puzzle input of this day.
+    pub fn default_input(&self) -> PathBuf {
+        self.data_dir().join("input.txt")
+    }
+}
+
+fn parse_boxed<S>(input: &str) -> Result<Box<dyn Solution>>
+where
+    S: Solution + 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+/// Returns the day with the given `number`, if it has a solution.
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::read_all;
+    use crate::solution::Answer;
+
+    #[test]
+    fn days_are_ordered_and_unique() {
+        assert!(DAYS.windows(2).all(|days| days[0].number < days[1].number));
+    }
+
+    #[test]
+    fn solve_test_input() {
+        let day = find(1).unwrap();
+        let input = read_all("data/day1/test.txt").unwrap();
+        let solution = day.parse(&input).unwrap();
+        assert_eq!(solution.part_one().unwrap(), Answer::Unsigned(7));
+        assert_eq!(solution.part_two().unwrap(), Some(Answer::Unsigned(5)));
+        assert!(find(25).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::cli::Part;
+use crate::json::Json;
+use aoc2021::{registry::Day, Answer, Error, Result};
+use std::time::{Duration, Instant};
+
+/// Answers to the selected parts of a day and how long it took to parse the
+/// input and to compute each of them.
+pub struct DayResult {
+    pub day: &'static Day,
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+pub struct PartResult {
+    pub part: Part,
+    /// `None` while the part is not solved yet, or the error if the input
+    /// has no answer.
+    pub answer: Result<Option<Answer>>,
+    pub time: Duration,
+}
+
+/// Parses `input` and solves the selected `part` of `day`.
+pub fn solve(
+    day: &'static Day,
+    input: &str,
+    part: Option<Part>,
+) -> Result<DayResult> {
+    let start = Instant::now();
+    let solution = day.parse(input)?;
+    let parse_time = start.elapsed();
+
+    let mut parts = vec![];
+    if Part::One.is_selected(part) {
+        let start = Instant::now();
+        let answer = solution.part_one().map(Some);
+        parts.push(PartResult {
+            part: Part::One,
+            answer,
+            time: start.elapsed(),
+        });
+    }
+
+    if Part::Two.is_selected(part) {
+        let start = Instant::now();
+        let answer = solution.part_two();
+        parts.push(PartResult {
+            part: Part::Two,
+            answer,
+            time: start.elapsed(),
+        });
+    }
+
+    Ok(DayResult {
+        day,
+        parse_time,
+        parts,
+    })
+}
+
+impl DayResult {
+    /// True, if any part could not be solved because of an error.
+    pub fn has_errors(&self) -> bool {
+        self.parts.iter().any(|result| result.answer.is_err())
+    }
+
+    /// Prints the answer to each part. Multi-line answers start on a new
+    /// line.
+    pub fn print(&self) {
+        for result in &self.parts {
+            let answer = match &result.answer {
+                Ok(Some(Answer::Text(text))) if text.contains('\n') => {
+                    format!("\n{}", text)
+                }
+                Ok(Some(answer)) => answer.to_string(),
+                Ok(None) => "not solved yet.".to_string(),
+                Err(e) => format!("error: {}", e),
+            };
+            println!(
+                "Solution for day {} ({}), part {}: {}",
+                self.day.number, self.day.title, result.part, answer
+            );
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        let parts = self
+            .parts
+            .iter()
+            .map(|result| match &result.answer {
+                Ok(answer) => Json::object([
+                    ("part", Json::from(result.part.number())),
+                    ("answer", answer.as_ref().into()),
+                    ("type", Json::from(answer_type(answer))),
+                    ("time_ns", Json::from(result.time)),
+                ]),
+                Err(e) => Json::object([
+                    ("part", Json::from(result.part.number())),
+                    ("error", Json::from(e.to_string())),
+                    ("time_ns", Json::from(result.time)),
+                ]),
+            })
+            .collect();
+
+        Json::object([
+            ("day", Json::from(self.day.number)),
+            ("title", Json::from(self.day.title)),
+            ("parse_time_ns", Json::from(self.parse_time)),
+            ("parts", Json::Array(parts)),
+        ])
+    }
+}
+
+/// JSON entry of a `day` which could not be solved because of `error`.
+pub fn error_json(day: &Day, error: &Error) -> Json {
+    Json::object([
+        ("day", Json::from(day.number)),
+        ("title", Json::from(day.title)),
+        ("error", Json::from(error.to_string())),
+    ])
+}
+
+fn answer_type(answer: &Option<Answer>) -> &'static str {
+    match answer {
+        Some(Answer::Unsigned(_)) => "unsigned",
+        Some(Answer::Signed(_)) => "signed",
+        Some(Answer::Text(_)) => "text",
+        None => "unsolved",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2021::registry;
+
+    #[test]
+    fn day_13_paper_as_json_string() {
+        let day = registry::find(13).unwrap();
+        let input = "0,0\n4,0\n\nfold along x=2\n";
+        let result = solve(day, input, Some(Part::Two)).unwrap();
+
+        let Json::Object(fields) = result.to_json() else {
+            panic!("Expected a JSON object");
+        };
+        let parts = &fields.iter().find(|(key, _)| key == "parts").unwrap().1;
+        let Json::Array(parts) = parts else {
+            panic!("Expected an array of parts");
+        };
+        let Json::Object(part) = &parts[0] else {
+            panic!("Expected a JSON object");
+        };
+        assert_eq!(part[0], ("part".to_string(), Json::Unsigned(2)));
+        assert_eq!(part[1], ("answer".to_string(), Json::from("#.")));
+        assert_eq!(part[2], ("type".to_string(), Json::from("text")));
+    }
+}
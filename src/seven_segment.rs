@@ -0,0 +1,305 @@
+use std::str::FromStr;
+
+use crate::error::{column_of, Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Segments of the digits 0 to 9 on an intact display.
+const DIGITS: [Pattern; 10] = [
+    Pattern::of("abcefg"),
+    Pattern::of("cf"),
+    Pattern::of("acdeg"),
+    Pattern::of("acdfg"),
+    Pattern::of("bcdf"),
+    Pattern::of("abdfg"),
+    Pattern::of("abdefg"),
+    Pattern::of("acf"),
+    Pattern::of("abcdefg"),
+    Pattern::of("abcdfg"),
+];
+
+/// Set of the wires or segments `a` to `g`, as bits 0 to 6.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pattern(u8);
+
+/// Which segment each wire of a display is connected to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WireMapping {
+    /// `segments[w]` is the segment of wire `w`, both as 0 for `a` etc.
+    segments: [u8; 7],
+}
+
+/// One line of notes: the ten unique signal patterns of a display and the
+/// four patterns of its output value.
+#[derive(Debug)]
+pub struct DisplayNote {
+    patterns: [Pattern; 10],
+    output: [Pattern; 4],
+}
+
+#[derive(Debug)]
+pub struct SevenSegmentNotes {
+    notes: Vec<DisplayNote>,
+}
+
+impl Pattern {
+    /// Pattern of the valid `wires`, for constants.
+    const fn of(wires: &str) -> Pattern {
+        let wires = wires.as_bytes();
+        let mut pattern = 0;
+        let mut idx = 0;
+        while idx < wires.len() {
+            pattern |= 1 << (wires[idx] - b'a');
+            idx += 1;
+        }
+        Pattern(pattern)
+    }
+
+    /// Number of wires in the pattern.
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, wire: u8) -> bool {
+        self.0 & (1 << wire) != 0
+    }
+
+    /// True, if the pattern shows 1, 4, 7 or 8, the only digits with their
+    /// number of segments.
+    pub fn has_unique_length(&self) -> bool {
+        matches!(self.len(), 2 | 3 | 4 | 7)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    /// Parses wires like `cfbegad`.
+    fn from_str(wires: &str) -> Result<Pattern> {
+        let mut pattern = 0;
+        for (idx, wire) in wires.chars().enumerate() {
+            if !('a'..='g').contains(&wire) {
+                return Err(Error::parse(
+                    idx,
+                    format!("Expected a wire from a to g, got {:?}", wire),
+                ));
+            }
+            pattern |= 1 << (wire as u8 - b'a');
+        }
+
+        Ok(Pattern(pattern))
+    }
+}
+
+impl WireMapping {
+    /// Segment, which `wire` (`a` to `g`) is connected to, or `None` for
+    /// other characters.
+    pub fn segment(&self, wire: char) -> Option<char> {
+        let index = "abcdefg".find(wire)?;
+        Some((b'a' + self.segments[index]) as char)
+    }
+
+    /// Digit shown by the wires in `pattern`, if they light up a valid digit.
+    pub fn decode(&self, pattern: Pattern) -> Option<u32> {
+        let segments = (0..7u8)
+            .filter(|&wire| pattern.contains(wire))
+            .fold(0, |segments, wire| {
+                segments | 1 << self.segments[wire as usize]
+            });
+
+        DIGITS
+            .iter()
+            .position(|&digit| digit == Pattern(segments))
+            .map(|digit| digit as u32)
+    }
+}
+
+impl DisplayNote {
+    /// Deduces which wire is connected to which segment from the ten signal
+    /// patterns. Returns `None` if the patterns are not the ten digits of
+    /// any mapping.
+    ///
+    /// Over all ten digits, the segments `b`, `e` and `f` are lit a unique
+    /// number of times (6, 4 and 9). `a` and `c` are both lit 8 times, but
+    /// only `c` is part of 1. `d` and `g` are both lit 7 times, but only `d`
+    /// is part of 4.
+    pub fn deduce_mapping(&self) -> Option<WireMapping> {
+        let one = self.patterns.iter().find(|p| p.len() == 2)?;
+        let four = self.patterns.iter().find(|p| p.len() == 4)?;
+
+        let mut segments = [0; 7];
+        for (wire, segment) in segments.iter_mut().enumerate() {
+            let wire = wire as u8;
+            let lit =
+                self.patterns.iter().filter(|p| p.contains(wire)).count();
+            let name = match (lit, one.contains(wire), four.contains(wire)) {
+                (4, _, _) => 'e',
+                (6, _, _) => 'b',
+                (9, _, _) => 'f',
+                (8, true, _) => 'c',
+                (8, false, _) => 'a',
+                (7, _, true) => 'd',
+                (7, _, false) => 'g',
+                _ => return None,
+            };
+            *segment = name as u8 - b'a';
+        }
+
+        let mapping = WireMapping { segments };
+        let mut digits: Vec<u32> = self
+            .patterns
+            .iter()
+            .map(|&pattern| mapping.decode(pattern))
+            .collect::<Option<_>>()?;
+        digits.sort_unstable();
+        (digits == (0..10).collect::<Vec<_>>()).then_some(mapping)
+    }
+
+    /// Decodes the four digits of the output to a number.
+    pub fn output_value(&self) -> Option<u32> {
+        let mapping = self.deduce_mapping()?;
+        self.output.iter().try_fold(0, |value, &pattern| {
+            Some(value * 10 + mapping.decode(pattern)?)
+        })
+    }
+}
+
+impl FromStr for DisplayNote {
+    type Err = Error;
+
+    /// Parses ten signal patterns and four output patterns, separated by
+    /// `|`, e.g. `be cfbegad ... edb | fdgacbe cefdb cefbgd gcbe`.
+    fn from_str(line: &str) -> Result<DisplayNote> {
+        let (patterns, output) = line.split_once('|').ok_or_else(|| {
+            Error::parse(0, "Expected signal patterns | output value")
+        })?;
+
+        let parse_patterns = |part: &str| -> Result<Vec<Pattern>> {
+            part.split_whitespace()
+                .map(|wires| {
+                    wires.parse().map_err(|e: Error| {
+                        e.offset_columns(column_of(line, wires))
+                    })
+                })
+                .collect()
+        };
+
+        let patterns: [Pattern; 10] = parse_patterns(patterns)?
+            .try_into()
+            .map_err(|_| Error::parse(0, "Expected 10 signal patterns"))?;
+        let output: [Pattern; 4] =
+            parse_patterns(output)?.try_into().map_err(|_| {
+                Error::parse(
+                    column_of(line, output),
+                    "Expected 4 output patterns",
+                )
+            })?;
+
+        let note = DisplayNote { patterns, output };
+        if note.output_value().is_none() {
+            return Err(Error::parse(
+                0,
+                "Patterns do not match the digits of a seven-segment display",
+            ));
+        }
+
+        Ok(note)
+    }
+}
+
+impl SevenSegmentNotes {
+    /// Number of digits 1, 4, 7 and 8 in all output values.
+    pub fn count_unique_digits(&self) -> usize {
+        self.notes
+            .iter()
+            .flat_map(|note| note.output.iter())
+            .filter(|pattern| pattern.has_unique_length())
+            .count()
+    }
+
+    /// Sum of all decoded output values.
+    pub fn sum_of_outputs(&self) -> u32 {
+        self.notes
+            .iter()
+            .map(|note| note.output_value().expect("Validated when parsing"))
+            .sum()
+    }
+}
+
+impl FromStr for SevenSegmentNotes {
+    type Err = Error;
+
+    /// Parses one `DisplayNote` per line.
+    fn from_str(input: &str) -> Result<SevenSegmentNotes> {
+        let notes = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                line.parse().map_err(|e: Error| e.offset_lines(idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SevenSegmentNotes { notes })
+    }
+}
+
+impl Solution for SevenSegmentNotes {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.count_unique_digits().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.sum_of_outputs().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn deduce_mapping() {
+        let note: DisplayNote = "acedgfb cdfbe gcdfa fbcad dab cefabd \
+            cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf"
+            .parse()
+            .unwrap();
+        let mapping = note.deduce_mapping().unwrap();
+        let segments: Option<String> = "abcdefg"
+            .chars()
+            .map(|wire| mapping.segment(wire))
+            .collect();
+        assert_eq!(segments.as_deref(), Some("cfgabde"));
+        assert_eq!(mapping.segment('h'), None);
+        assert_eq!(mapping.segment('A'), None);
+        assert_eq!(mapping.decode("ab".parse().unwrap()), Some(1));
+        assert_eq!(note.output_value(), Some(5353));
+    }
+
+    #[test]
+    fn decode_notes() {
+        let notes =
+            SevenSegmentNotes::from_file("data/day8/test.txt").unwrap();
+        assert_eq!(notes.count_unique_digits(), 26);
+        assert_eq!(notes.sum_of_outputs(), 61229);
+    }
+
+    #[test]
+    fn reject_invalid_notes() {
+        assert_eq!(
+            "ab cx | ab".parse::<DisplayNote>().unwrap_err().to_string(),
+            "1:5: Expected a wire from a to g, got 'x'"
+        );
+        assert!("ab ab ab ab ab ab ab ab ab ab | ab ab ab ab"
+            .parse::<DisplayNote>()
+            .is_err());
+    }
+}
@@ -0,0 +1,459 @@
+use std::fmt;
+use std::iter::{Enumerate, Peekable};
+use std::ops::Add;
+use std::str::{Chars, FromStr};
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Pairs nested inside this many pairs explode.
+const MAX_DEPTH: usize = 4;
+
+/// Regular number of a snailfish number, nested inside `depth` pairs.
+///
+/// Parsed values fit into `u32`, so exploding pairs can add them up without
+/// overflowing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Regular {
+    value: u64,
+    depth: usize,
+}
+
+/// Snailfish number like `[[1,2],3]`.
+///
+/// The pairs are not stored as a tree, but as the list of regular numbers
+/// from left to right with their depth. This is enough to restore the
+/// tree, and makes finding the neighbours of an exploding pair easy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnailfishNumber {
+    regulars: Vec<Regular>,
+}
+
+/// Homework of the snailfish, one number per line.
+#[derive(Debug)]
+pub struct SnailfishHomework {
+    numbers: Vec<SnailfishNumber>,
+}
+
+impl SnailfishNumber {
+    /// Explodes the leftmost pair of regular numbers nested inside four
+    /// pairs. Its left value is added to the first regular number to the
+    /// left, its right value to the first one to the right, and the pair is
+    /// replaced by 0.
+    ///
+    /// Returns whether a pair exploded.
+    pub fn explode(&mut self) -> bool {
+        // The first regular number too deep, which is followed by one at
+        // the same depth, is the left one of the leftmost pair of regular
+        // numbers. Any pair further left would contain such a pair itself.
+        let Some(left) = self.regulars.windows(2).position(|pair| {
+            pair[0].depth > MAX_DEPTH && pair[0].depth == pair[1].depth
+        }) else {
+            return false;
+        };
+        let right = self.regulars.remove(left + 1);
+
+        if left > 0 {
+            self.regulars[left - 1].value += self.regulars[left].value;
+        }
+        if let Some(next) = self.regulars.get_mut(left + 1) {
+            next.value += right.value;
+        }
+        self.regulars[left] = Regular {
+            value: 0,
+            depth: right.depth - 1,
+        };
+
+        true
+    }
+
+    /// Splits the leftmost regular number of 10 or more into a pair of
+    /// its half, rounded down and up.
+    ///
+    /// Returns whether a number split.
+    pub fn split(&mut self) -> bool {
+        let Some(idx) = self.regulars.iter().position(|r| r.value >= 10)
+        else {
+            return false;
+        };
+
+        let Regular { value, depth } = self.regulars[idx];
+        self.regulars[idx] = Regular {
+            value: value / 2,
+            depth: depth + 1,
+        };
+        self.regulars.insert(
+            idx + 1,
+            Regular {
+                value: value - value / 2,
+                depth: depth + 1,
+            },
+        );
+
+        true
+    }
+
+    /// Explodes and splits until neither is possible, always exploding
+    /// first.
+    fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    /// Three times the magnitude of the left element of each pair plus
+    /// twice the magnitude of the right one.
+    pub fn magnitude(&self) -> u64 {
+        self.fold(&mut 0, 0, &mut |value| value, &mut |left, right| {
+            3 * left + 2 * right
+        })
+    }
+
+    /// Combines the regular numbers from `*idx` on bottom up, which make
+    /// up an element nested inside `depth` pairs.
+    fn fold<T>(
+        &self,
+        idx: &mut usize,
+        depth: usize,
+        regular: &mut impl FnMut(u64) -> T,
+        pair: &mut impl FnMut(T, T) -> T,
+    ) -> T {
+        let current = self.regulars[*idx];
+        if current.depth == depth {
+            *idx += 1;
+            return regular(current.value);
+        }
+
+        let left = self.fold(idx, depth + 1, regular, pair);
+        let right = self.fold(idx, depth + 1, regular, pair);
+        pair(left, right)
+    }
+}
+
+/// Adds two numbers by making them a pair, which is reduced afterwards.
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
+
+    fn add(self, other: SnailfishNumber) -> SnailfishNumber {
+        let mut regulars = self.regulars;
+        regulars.extend(other.regulars);
+        for regular in &mut regulars {
+            regular.depth += 1;
+        }
+
+        let mut sum = SnailfishNumber { regulars };
+        sum.reduce();
+        sum
+    }
+}
+
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let notation = self.fold(
+            &mut 0,
+            0,
+            &mut |value| value.to_string(),
+            &mut |left, right| format!("[{},{}]", left, right),
+        );
+        f.write_str(&notation)
+    }
+}
+
+/// Recursive descent parser for the notation of a snailfish number.
+struct Parser<'a> {
+    chars: Peekable<Enumerate<Chars<'a>>>,
+    /// Column behind the last character.
+    end: usize,
+    regulars: Vec<Regular>,
+}
+
+impl Parser<'_> {
+    /// Next character with its column, or `None` at the end of the line.
+    fn peek(&mut self) -> (usize, Option<char>) {
+        match self.chars.peek() {
+            Some(&(column, char)) => (column, Some(char)),
+            None => (self.end, None),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            (_, Some(char)) if char == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            (column, char) => {
+                Err(unexpected(column, char, &format!("{:?}", expected)))
+            }
+        }
+    }
+
+    /// Parses the element starting at the next character, nested inside
+    /// `depth` pairs.
+    fn element(&mut self, depth: usize) -> Result<()> {
+        match self.peek() {
+            (column, Some('[')) => {
+                // Pairs nested inside 4 pairs are accepted, as they occur
+                // in numbers which are not reduced yet. Deeper pairs would
+                // only recurse further.
+                if depth > MAX_DEPTH {
+                    return Err(Error::parse(
+                        column,
+                        "Pairs must not be nested inside more than 4 pairs",
+                    ));
+                }
+                self.chars.next();
+                self.element(depth + 1)?;
+                self.expect(',')?;
+                self.element(depth + 1)?;
+                self.expect(']')
+            }
+            (column, Some(char)) if char.is_ascii_digit() => {
+                let mut value: u32 = 0;
+                while let (_, Some(char)) = self.peek() {
+                    let Some(digit) = char.to_digit(10) else {
+                        break;
+                    };
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit))
+                        .ok_or_else(|| {
+                            Error::parse(column, "Regular number is too large")
+                        })?;
+                    self.chars.next();
+                }
+                self.regulars.push(Regular {
+                    value: value.into(),
+                    depth,
+                });
+                Ok(())
+            }
+            (column, char) => {
+                Err(unexpected(column, char, "a pair or a number"))
+            }
+        }
+    }
+}
+
+/// Error for the character `char` at `column`, or the end of the line if
+/// there is none, where `expected` should be.
+fn unexpected(column: usize, char: Option<char>, expected: &str) -> Error {
+    let got = match char {
+        Some(char) => format!("{:?}", char),
+        None => "the end of the line".to_string(),
+    };
+    Error::parse(column, format!("Expected {}, got {}", expected, got))
+}
+
+impl FromStr for SnailfishNumber {
+    type Err = Error;
+
+    /// Parses nested pairs `[a,b]` of regular numbers or pairs. The number
+    /// itself has to be a pair.
+    fn from_str(line: &str) -> Result<SnailfishNumber> {
+        let line = line.trim_end();
+        let mut parser = Parser {
+            chars: line.chars().enumerate().peekable(),
+            end: line.chars().count(),
+            regulars: vec![],
+        };
+
+        let (column, char) = parser.peek();
+        if char != Some('[') {
+            return Err(unexpected(column, char, "a pair"));
+        }
+        parser.element(0)?;
+        if let (column, Some(char)) = parser.peek() {
+            return Err(Error::parse(
+                column,
+                format!("Expected the end of the line, got {:?}", char),
+            ));
+        }
+
+        Ok(SnailfishNumber {
+            regulars: parser.regulars,
+        })
+    }
+}
+
+impl SnailfishHomework {
+    /// Sum of all numbers, added from top to bottom.
+    pub fn sum(&self) -> Option<SnailfishNumber> {
+        self.numbers
+            .iter()
+            .cloned()
+            .reduce(|sum, number| sum + number)
+    }
+
+    /// Largest magnitude of the sum of two different numbers, in either
+    /// order, since snailfish addition is not commutative.
+    pub fn largest_magnitude_of_two(&self) -> Option<u64> {
+        let numbers = &self.numbers;
+        (0..numbers.len())
+            .flat_map(|i| (0..numbers.len()).map(move |j| (i, j)))
+            .filter(|(i, j)| i != j)
+            .map(|(i, j)| {
+                (numbers[i].clone() + numbers[j].clone()).magnitude()
+            })
+            .max()
+    }
+}
+
+impl FromStr for SnailfishHomework {
+    type Err = Error;
+
+    /// Parses one `SnailfishNumber` per line.
+    fn from_str(input: &str) -> Result<SnailfishHomework> {
+        let numbers = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                line.parse().map_err(|e: Error| e.offset_lines(idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SnailfishHomework { numbers })
+    }
+}
+
+impl Solution for SnailfishHomework {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let sum = self
+            .sum()
+            .ok_or_else(|| Error::invalid("The homework has no numbers."))?;
+        Ok(sum.magnitude().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let magnitude = self.largest_magnitude_of_two().ok_or_else(|| {
+            Error::invalid("The homework has fewer than two numbers.")
+        })?;
+        Ok(Some(magnitude.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    fn number(notation: &str) -> SnailfishNumber {
+        notation.parse().unwrap()
+    }
+
+    #[test]
+    fn explode_and_split() {
+        let mut explode = number("[[[[[9,8],1],2],3],4]");
+        assert!(explode.explode());
+        assert_eq!(explode.to_string(), "[[[[0,9],2],3],4]");
+
+        let mut explode = number("[7,[6,[5,[4,[3,2]]]]]");
+        assert!(explode.explode());
+        assert_eq!(explode.to_string(), "[7,[6,[5,[7,0]]]]");
+        assert!(!explode.explode());
+
+        // The sum of unreduced numbers nests 1 inside 5 pairs, but [2,3]
+        // explodes first, as it is the leftmost pair of regular numbers.
+        assert_eq!(
+            (number("[[[[1,[2,3]],4],5],6]") + number("[1,1]")).to_string(),
+            "[[[[0,7],5],6],[1,1]]"
+        );
+
+        let mut split = number("[15,[0,13]]");
+        assert!(split.split());
+        assert_eq!(split.to_string(), "[[7,8],[0,13]]");
+    }
+
+    #[test]
+    fn add_numbers() {
+        assert_eq!(
+            (number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]"))
+                .to_string(),
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+
+        let homework: SnailfishHomework =
+            "[1,1]\n[2,2]\n[3,3]\n[4,4]\n[5,5]".parse().unwrap();
+        assert_eq!(
+            homework.sum().unwrap().to_string(),
+            "[[[[3,0],[5,3]],[4,4]],[5,5]]"
+        );
+    }
+
+    #[test]
+    fn magnitude() {
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+        assert_eq!(
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]")
+                .magnitude(),
+            3488
+        );
+    }
+
+    #[test]
+    fn do_homework() {
+        let homework =
+            SnailfishHomework::from_file("data/day18/test.txt").unwrap();
+        let sum = homework.sum().unwrap();
+        assert_eq!(
+            sum.to_string(),
+            "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+        );
+        assert_eq!(sum.magnitude(), 4140);
+        assert_eq!(homework.largest_magnitude_of_two(), Some(3993));
+
+        let single = SnailfishHomework::parse("[1,2]").unwrap();
+        assert_eq!(single.part_one().unwrap(), Answer::Unsigned(7));
+        assert_eq!(
+            single.part_two().unwrap_err().to_string(),
+            "The homework has fewer than two numbers."
+        );
+        assert!(SnailfishHomework::parse("").unwrap().part_one().is_err());
+    }
+
+    #[test]
+    fn reject_invalid_numbers() {
+        assert_eq!(
+            "[1,2]\n[[1,2]3]"
+                .parse::<SnailfishHomework>()
+                .unwrap_err()
+                .to_string(),
+            "2:7: Expected ',', got '3'"
+        );
+        assert_eq!(
+            "[1,[2,3]"
+                .parse::<SnailfishNumber>()
+                .unwrap_err()
+                .to_string(),
+            "1:9: Expected ']', got the end of the line"
+        );
+        assert!("[1,2]]".parse::<SnailfishNumber>().is_err());
+        assert_eq!(
+            "[[[[[[1,2],3],4],5],6],7]"
+                .parse::<SnailfishNumber>()
+                .unwrap_err()
+                .to_string(),
+            "1:6: Pairs must not be nested inside more than 4 pairs"
+        );
+        assert_eq!(
+            "5".parse::<SnailfishNumber>().unwrap_err().to_string(),
+            "1:1: Expected a pair, got '5'"
+        );
+        assert_eq!(
+            "[½,1]x".parse::<SnailfishNumber>().unwrap_err().to_string(),
+            "1:2: Expected a pair or a number, got '½'"
+        );
+        assert!("".parse::<SnailfishNumber>().is_err());
+    }
+
+    #[test]
+    fn explode_large_numbers() {
+        let mut large =
+            number("[[[[[4294967295,4294967295],4294967295],1],1],1]");
+        assert!(large.explode());
+        assert_eq!(large.to_string(), "[[[[0,8589934590],1],1],1]");
+    }
+}
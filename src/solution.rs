@@ -0,0 +1,75 @@
+use crate::error::Result;
+use std::fmt;
+
+/// Answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    /// Answers which are not a number, like the code on the folded paper of
+    /// day 13.
+    Text(String),
+}
+
+/// Common interface of the solutions of all days.
+pub trait Solution {
+    /// Parses the puzzle `input`.
+    fn parse(input: &str) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Errors if the input has no answer, although it could be parsed.
+    fn part_one(&self) -> Result<Answer>;
+
+    /// Returns `None` while part two of the day is not solved yet. Errors
+    /// like `part_one`.
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(None)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(number) => write!(f, "{}", number),
+            Answer::Signed(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(number: u32) -> Self {
+        Answer::Unsigned(number.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(number: u64) -> Self {
+        Answer::Unsigned(number)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        Answer::Unsigned(number as u64)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(number: i32) -> Self {
+        Answer::Signed(number.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(number: i64) -> Self {
+        Answer::Signed(number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
@@ -1,66 +1,123 @@
-use crate::file_handler::read_lines;
+use crate::error::{parse_token, Error, Result};
+use crate::file_handler::FromInput;
+use crate::solution::{Answer, Solution};
 use std::path::Path;
-use std::u64::MAX;
+use std::str::FromStr;
 
-pub fn count_increases<P>(file: P) -> u64
+/// Depth measurements of a sonar sweep, one per line of the input.
+#[derive(Debug)]
+pub struct SonarSweep {
+    depths: Vec<u64>,
+}
+
+pub fn count_increases<P>(file: P) -> Result<u64>
+where
+    P: AsRef<Path>,
+{
+    Ok(SonarSweep::from_file(file)?.count_increases())
+}
+
+pub fn count_increases_sliding<P>(file: P) -> Result<u64>
 where
     P: AsRef<Path>,
 {
-    let mut previous_value = MAX;
-    let mut increases = 0;
+    Ok(SonarSweep::from_file(file)?.count_increases_sliding())
+}
 
-    if let Ok(lines) = read_lines(file) {
-        for line in lines {
-            let number = line.unwrap_or_default().parse().unwrap_or(0);
+impl SonarSweep {
+    /// Number of measurements which are larger than the previous one.
+    pub fn count_increases(&self) -> u64 {
+        let mut previous_value = u64::MAX;
+        let mut increases = 0;
+
+        for &number in &self.depths {
             if number > previous_value {
                 increases += 1;
             }
 
             previous_value = number;
         }
+
+        increases
     }
 
-    increases
-}
+    /// Number of sums of three-measurement sliding windows which are larger
+    /// than the previous sum.
+    pub fn count_increases_sliding(&self) -> u64 {
+        let all_windows = self.depths.windows(3);
 
-pub fn count_increases_sliding<P>(file: P) -> u64
-where
-    P: AsRef<Path>,
-{
-    let all_numbers = read_lines(file)
-        .expect("Could not read lines of file!")
-        .map(|line| line.unwrap_or_default().parse().unwrap_or(0))
-        .collect::<Vec<_>>();
-    let all_windows = all_numbers.windows(3);
-
-    let mut previous_window_sum: u64 = MAX;
-    let mut increases: u64 = 0;
-
-    for window in all_windows {
-        let window_sum = window.iter().sum();
-        if window_sum > previous_window_sum {
-            increases += 1;
+        let mut previous_window_sum: u64 = u64::MAX;
+        let mut increases: u64 = 0;
+
+        for window in all_windows {
+            let window_sum = window.iter().sum();
+            if window_sum > previous_window_sum {
+                increases += 1;
+            }
+
+            previous_window_sum = window_sum;
         }
 
-        previous_window_sum = window_sum;
+        increases
+    }
+}
+
+impl FromStr for SonarSweep {
+    type Err = Error;
+
+    /// Parses one depth measurement per line of `report`.
+    fn from_str(report: &str) -> Result<SonarSweep> {
+        let depths = report
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                parse_token(line, 0).map_err(|e: Error| e.offset_lines(idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SonarSweep { depths })
+    }
+}
+
+impl Solution for SonarSweep {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.count_increases().into())
     }
 
-    increases
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.count_increases_sliding().into()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{count_increases, count_increases_sliding};
+    use super::{count_increases, count_increases_sliding, SonarSweep};
+    use crate::file_handler::FromInput;
 
     #[test]
     fn test_increases() {
-        let increases = count_increases("data/day1/test.txt");
-        assert_eq!(increases, 7);
+        let sweep = SonarSweep::from_file("data/day1/test.txt").unwrap();
+        assert_eq!(sweep.count_increases(), 7);
+        assert_eq!(count_increases("data/day1/test.txt").unwrap(), 7);
     }
 
     #[test]
     fn test_increases_sliding() {
-        let increases = count_increases_sliding("data/day1/test.txt");
-        assert_eq!(increases, 5);
+        let sweep = SonarSweep::from_file("data/day1/test.txt").unwrap();
+        assert_eq!(sweep.count_increases_sliding(), 5);
+        assert_eq!(count_increases_sliding("data/day1/test.txt").unwrap(), 5);
+    }
+
+    #[test]
+    fn invalid_depth() {
+        let error = SonarSweep::from_file("data/day2/test.txt").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "data/day2/test.txt:1:1: Could not parse number \"forward 5\""
+        );
     }
 }
@@ -1,12 +1,15 @@
+use crate::error::{column_of, parse_token, Result};
+use crate::grid::Grid;
+
 #[derive(Clone, Copy, Debug)]
 struct BingoNumber {
     number: u32,
     marked: bool,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BingoBoard {
-    board: Vec<Vec<BingoNumber>>,
+    board: Grid<BingoNumber>,
 }
 
 impl BingoNumber {
@@ -25,39 +28,40 @@ impl BingoNumber {
 impl BingoBoard {
     /// Creates a new bingo board from a block
     /// of text `lines` in the following format:
-    /// ```
+    /// ```text
     /// 22 13 17 11  0
     ///  8  2 23  4 24
     /// 21  9 14 16  7
     ///  6 10  3 18  5
     ///  1 12 20 15 19
     /// ```
-    pub fn build_from(lines: Vec<String>) -> BingoBoard {
-        BingoBoard {
-            board: {
-                lines
-                    .into_iter()
-                    .map(|line| {
-                        line.split_whitespace()
-                            .map(|number_str| {
-                                let number =
-                                    number_str.parse().unwrap_or_default();
-                                BingoNumber::new(number)
-                            })
-                            .collect::<Vec<_>>()
+    pub fn build_from(lines: Vec<String>) -> Result<BingoBoard> {
+        let board = lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                line.split_whitespace()
+                    .map(|number_str| {
+                        parse_token(number_str, column_of(line, number_str))
+                            .map(BingoNumber::new)
                     })
-                    .collect::<Vec<_>>()
-            },
-        }
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(|e| e.offset_lines(row))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(BingoBoard {
+            board: Grid::from_rows(board)?,
+        })
     }
 
     #[allow(dead_code)]
-    fn row(&self, index: usize) -> &Vec<BingoNumber> {
-        &self.board[index]
+    fn row(&self, index: usize) -> &[BingoNumber] {
+        self.board.row(index)
     }
 
     pub fn mark_number(&mut self, drawn_number: &u32) {
-        for number in self.board.iter_mut().flatten() {
+        for number in self.board.iter_mut() {
             if number.number == *drawn_number {
                 number.mark()
             }
@@ -67,27 +71,19 @@ impl BingoBoard {
     pub fn has_won(&self) -> bool {
         let has_full_row = self
             .board
-            .iter()
+            .rows()
             .any(|row| row.iter().all(|number| number.marked));
-        if has_full_row {
-            return true;
-        }
 
-        let cols = self.board[0].len();
-        for col_idx in 0..cols {
-            let full_col = self.board.iter().all(|row| row[col_idx].marked);
-            if full_col {
-                return true;
-            }
-        }
-
-        false
+        has_full_row
+            || self
+                .board
+                .columns()
+                .any(|mut column| column.all(|number| number.marked))
     }
 
     pub fn score(&self) -> u32 {
         self.board
             .iter()
-            .flatten()
             .filter(|number| !number.marked)
             .map(|number| number.number)
             .sum()
@@ -96,18 +92,19 @@ impl BingoBoard {
 
 #[cfg(test)]
 mod tests {
-    use crate::file_handler::read_lines;
+    use crate::file_handler::read_all;
 
     use super::*;
 
     #[test]
     fn parse_bingo_board() {
-        let lines = read_lines("data/day4/test_board.txt")
+        let lines = read_all("data/day4/test_board.txt")
             .unwrap()
-            .map(|line| line.unwrap())
+            .lines()
+            .map(|line| line.to_string())
             .collect::<Vec<_>>();
 
-        let board = BingoBoard::build_from(lines);
+        let board = BingoBoard::build_from(lines).unwrap();
         assert_eq!(
             board.row(0).iter().map(|bn| bn.number).collect::<Vec<_>>(),
             vec![22, 13, 17, 11, 0]
@@ -133,4 +130,16 @@ mod tests {
             vec![1, 12, 20, 15, 19]
         );
     }
+
+    #[test]
+    fn reject_invalid_board() {
+        let lines = vec!["1 2".to_string(), "3 x".to_string()];
+        assert_eq!(
+            BingoBoard::build_from(lines).unwrap_err().to_string(),
+            "2:3: Could not parse number \"x\""
+        );
+
+        let lines = vec!["1 2".to_string(), "3".to_string()];
+        assert!(BingoBoard::build_from(lines).is_err());
+    }
 }
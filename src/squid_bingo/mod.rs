@@ -1,10 +1,13 @@
 mod bingo_board;
-use bingo_board::BingoBoard;
+pub use bingo_board::BingoBoard;
 use std::path::Path;
+use std::str::FromStr;
 
-use crate::file_handler::read_all;
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::file_handler::FromInput;
+use crate::solution::{Answer, Solution};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BingoGame {
     // Boards of all players in the game
     boards: Vec<BingoBoard>,
@@ -18,48 +21,12 @@ pub struct BingoGameResult {
 }
 
 impl BingoGame {
-    /// Parses the contents of the given `file` and creates a new bingo game.
-    /// The file has to have a comma-separated list of numbers in the first
-    /// line. These are the numbers that are drawn in the game.
-    ///
-    /// After that, there need to be bingo boards in the format described in
-    /// `BingoBoard`. They have to be separated by empty lines.
-    ///
-    /// See data/day4/test.txt for an example.
-    pub fn build_from<P>(file: P) -> BingoGame
+    /// Reads a new bingo game from `file`, see `BingoGame::from_str`.
+    pub fn build_from<P>(file: P) -> Result<BingoGame>
     where
         P: AsRef<Path>,
     {
-        let game_data = read_all(file).expect("Could not read game file.");
-
-        let drawn_numbers: Vec<u32> = game_data
-            .lines()
-            .next()
-            .expect("Could not read first line of game file!")
-            .split(',')
-            .map(|number_str| number_str.parse().unwrap())
-            .collect();
-
-        /*
-            Boards are all separated by an empty line.
-            The first line of the file is the drawn numbers, so we skip it.
-        */
-        let boards_data = game_data.split("\n\n").skip(1);
-
-        let boards = boards_data
-            .map(|board_data| {
-                let lines = board_data
-                    .lines()
-                    .map(|line| line.to_string())
-                    .collect::<Vec<_>>();
-                BingoBoard::build_from(lines)
-            })
-            .collect();
-
-        BingoGame {
-            drawn_numbers,
-            boards,
-        }
+        BingoGame::from_file(file)
     }
 
     /// Simulate the game until the first board wins
@@ -109,6 +76,88 @@ impl BingoGame {
     }
 }
 
+impl FromStr for BingoGame {
+    type Err = Error;
+
+    /// Parses the given `game_data` and creates a new bingo game.
+    /// It has to have a comma-separated list of numbers in the first
+    /// line. These are the numbers that are drawn in the game.
+    ///
+    /// After that, there need to be bingo boards in the format described in
+    /// `BingoBoard`. They have to be separated by empty lines.
+    ///
+    /// See data/day4/test.txt for an example.
+    fn from_str(game_data: &str) -> Result<BingoGame> {
+        let lines = game_data.lines().collect::<Vec<_>>();
+        let first_line = lines
+            .first()
+            .ok_or_else(|| Error::parse(0, "Game file is empty."))?;
+
+        let drawn_numbers: Vec<u32> = first_line
+            .split(',')
+            .map(|number_str| {
+                parse_token(number_str, column_of(first_line, number_str))
+            })
+            .collect::<Result<_>>()?;
+
+        /*
+            Boards are all separated by an empty line.
+            The first line of the file is the drawn numbers, so we skip it.
+        */
+        let mut boards = vec![];
+        let mut start = 1;
+        while start < lines.len() {
+            if lines[start].trim().is_empty() {
+                start += 1;
+                continue;
+            }
+
+            let end = lines[start..]
+                .iter()
+                .position(|line| line.trim().is_empty())
+                .map_or(lines.len(), |length| start + length);
+            let board_lines =
+                lines[start..end].iter().map(|line| line.to_string());
+            boards.push(
+                BingoBoard::build_from(board_lines.collect())
+                    .map_err(|e| e.offset_lines(start))?,
+            );
+            start = end;
+        }
+
+        if boards.is_empty() {
+            return Err(Error::at(lines.len(), 0, "Game has no boards."));
+        }
+
+        Ok(BingoGame {
+            drawn_numbers,
+            boards,
+        })
+    }
+}
+
+impl Solution for BingoGame {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let result = self
+            .clone()
+            .simulate()
+            .ok_or_else(|| Error::invalid("No board wins the game."))?;
+        Ok(result.answer().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let result = self
+            .clone()
+            .simulate_until_end()
+            .ok_or_else(|| Error::invalid("Not every board wins the game."))?;
+        Ok(Some(result.answer().into()))
+    }
+}
+
 impl BingoGameResult {
     /// Provides answer to the challenge of day 4.
     pub fn answer(&self) -> u32 {
@@ -122,7 +171,7 @@ mod tests {
 
     #[test]
     fn build_bingo_game() {
-        let game = BingoGame::build_from("data/day4/test.txt");
+        let game = BingoGame::build_from("data/day4/test.txt").unwrap();
         assert_eq!(game.boards.len(), 3);
         assert_eq!(
             game.drawn_numbers,
@@ -135,7 +184,7 @@ mod tests {
 
     #[test]
     fn simulate_game() {
-        let mut game = BingoGame::build_from("data/day4/test.txt");
+        let mut game = BingoGame::build_from("data/day4/test.txt").unwrap();
         let result = game.simulate().unwrap();
         assert_eq!(result.winning_board_score, 188);
         assert_eq!(result.last_drawn_number, 24);
@@ -144,7 +193,7 @@ mod tests {
 
     #[test]
     fn simulate_game_until_end() {
-        let mut game = BingoGame::build_from("data/day4/test.txt");
+        let mut game = BingoGame::build_from("data/day4/test.txt").unwrap();
         let result = game.simulate_until_end().unwrap();
 
         println!("{:#?}", game);
@@ -153,4 +202,39 @@ mod tests {
         assert_eq!(result.winning_board_score, 148);
         assert_eq!(result.answer(), 1924);
     }
+
+    #[test]
+    fn build_bingo_game_from_str() {
+        let game: BingoGame = "1,2\n\n1 2\n3 4\n\n5 6\n7 8\n".parse().unwrap();
+        assert_eq!(game.boards.len(), 2);
+        assert_eq!(game.drawn_numbers, vec![1, 2]);
+
+        let error = "1,2\n\n1 2\n3 4\n\n5 6\n7 x\n"
+            .parse::<BingoGame>()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "7:3: Could not parse number \"x\"");
+    }
+
+    #[test]
+    fn no_winning_board() {
+        let game = BingoGame::parse("1\n\n2 3\n4 5").unwrap();
+        assert_eq!(
+            game.part_one().unwrap_err().to_string(),
+            "No board wins the game."
+        );
+        assert!(game.part_two().is_err());
+    }
+
+    #[test]
+    fn invalid_drawn_numbers() {
+        // A lone board has no line of drawn numbers, so its first row is
+        // read as one
+        let error = BingoGame::build_from("data/day4/test_board.txt")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "data/day4/test_board.txt:1:1: Could not parse number \"22 13 17 11  0\""
+        );
+    }
 }
@@ -1,5 +1,8 @@
-use crate::file_handler::read_lines;
+use crate::error::{parse_token, Error, Result};
+use crate::file_handler::FromInput;
+use crate::solution::{Answer, Solution};
 use std::path::Path;
+use std::str::FromStr;
 
 pub trait Submarine {
     /// Moves the submarine
@@ -11,25 +14,34 @@ pub trait Submarine {
     /// Moves the submarine based on a given list of instructions.
     /// Instructions are given as text-file with one line per instruction,
     /// formatted as described in `SubmarineMovement`.
-    fn change_position_from<P>(&mut self, instruction_file: P)
+    fn change_position_from<P>(&mut self, instruction_file: P) -> Result<()>
     where
         P: AsRef<Path>,
     {
-        let instructions = read_lines(instruction_file)
-            .expect("Could not read instruction file")
-            .map(|line| SubmarineMovement::from(&line.unwrap_or_default()));
+        self.follow(&Course::from_file(instruction_file)?);
+        Ok(())
+    }
 
-        for instruction in instructions {
-            self.change_position(instruction);
+    /// Moves the submarine along all instructions of the given `course`.
+    fn follow(&mut self, course: &Course) {
+        for instruction in &course.instructions {
+            self.change_position(*instruction);
         }
     }
 }
 
+/// List of instructions for the submarine, in the order they are given.
+pub struct Course {
+    instructions: Vec<SubmarineMovement>,
+}
+
+#[derive(Default)]
 pub struct SimpleSubmarine {
     depth: i64,
     position_h: i64,
 }
 
+#[derive(Default)]
 pub struct AimedSubmarine {
     depth: i64,
     position_h: i64,
@@ -37,39 +49,82 @@ pub struct AimedSubmarine {
 }
 
 /// Direction for the submarine to move in + distance
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum SubmarineMovement {
     Forward(i64),
     Down(i64),
     Up(i64),
-    Stay,
 }
 
-impl SubmarineMovement {
+impl FromStr for SubmarineMovement {
+    type Err = Error;
+
     /// Returns a SubmarineMovement parsed from the given `instruction` string.
     /// Example:
     ///     Given "forward 9" returns SubmarineMovement.Forward(9)
     ///
     /// Available instructions: forward, down, up
-    fn from(instruction: &str) -> SubmarineMovement {
-        let parts: Vec<_> = instruction.split(' ').collect();
-        let direction: &str = parts[0];
-        let distance: i64 = parts[1].parse().unwrap_or_else(|_| {
-            panic!(
-                "Could not parse direction of given instruction {}",
-                instruction
-            )
-        });
+    fn from_str(instruction: &str) -> Result<SubmarineMovement> {
+        let (direction, distance) =
+            instruction.split_once(' ').ok_or_else(|| {
+                Error::parse(
+                    0,
+                    format!(
+                        "Expected direction and distance: {}",
+                        instruction
+                    ),
+                )
+            })?;
+        let distance: i64 = parse_token(distance, direction.len() + 1)?;
 
         match direction {
-            "forward" => SubmarineMovement::Forward(distance),
-            "up" => SubmarineMovement::Up(distance),
-            "down" => SubmarineMovement::Down(distance),
-            &_ => SubmarineMovement::Stay,
+            "forward" => Ok(SubmarineMovement::Forward(distance)),
+            "up" => Ok(SubmarineMovement::Up(distance)),
+            "down" => Ok(SubmarineMovement::Down(distance)),
+            _ => Err(Error::parse(
+                0,
+                format!("Unknown direction {:?}", direction),
+            )),
         }
     }
 }
 
+impl FromStr for Course {
+    type Err = Error;
+
+    /// Parses a course from text with one line per instruction,
+    /// formatted as described in `SubmarineMovement`.
+    fn from_str(instructions: &str) -> Result<Course> {
+        let instructions = instructions
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.parse().map_err(|e: Error| e.offset_lines(idx))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Course { instructions })
+    }
+}
+
+impl Solution for Course {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let mut submarine = SimpleSubmarine::new();
+        submarine.follow(self);
+        Ok(submarine.position_hash().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let mut submarine = AimedSubmarine::new();
+        submarine.follow(self);
+        Ok(Some(submarine.position_hash().into()))
+    }
+}
+
 impl SimpleSubmarine {
     /// Create a new submarine at position 0, depth 0.
     pub fn new() -> SimpleSubmarine {
@@ -86,7 +141,6 @@ impl Submarine for SimpleSubmarine {
             SubmarineMovement::Forward(d) => self.position_h += d,
             SubmarineMovement::Up(d) => self.depth -= d,
             SubmarineMovement::Down(d) => self.depth += d,
-            SubmarineMovement::Stay => (),
         }
     }
 
@@ -114,7 +168,6 @@ impl Submarine for AimedSubmarine {
             }
             SubmarineMovement::Up(d) => self.aim -= d,
             SubmarineMovement::Down(d) => self.aim += d,
-            SubmarineMovement::Stay => (),
         }
     }
 
@@ -132,19 +185,28 @@ mod tests {
     #[test]
     fn movement_decode() {
         assert_eq!(
-            SubmarineMovement::from("forward 9"),
+            "forward 9".parse::<SubmarineMovement>().unwrap(),
             SubmarineMovement::Forward(9)
         );
         assert_eq!(
-            SubmarineMovement::from("up 42"),
+            "up 42".parse::<SubmarineMovement>().unwrap(),
             SubmarineMovement::Up(42)
         );
         assert_eq!(
-            SubmarineMovement::from("down 1"),
+            "down 1".parse::<SubmarineMovement>().unwrap(),
             SubmarineMovement::Down(1)
         );
     }
 
+    #[test]
+    fn invalid_movement() {
+        let error = "backward 3".parse::<SubmarineMovement>().unwrap_err();
+        assert_eq!(error.to_string(), "1:1: Unknown direction \"backward\"");
+
+        let error = "up x".parse::<SubmarineMovement>().unwrap_err();
+        assert_eq!(error.to_string(), "1:4: Could not parse number \"x\"");
+    }
+
     #[test]
     fn submarine_movement() {
         let mut submarine = SimpleSubmarine::new();
@@ -169,7 +231,9 @@ mod tests {
         let mut submarine = SimpleSubmarine::new();
         assert_eq!(submarine.position_hash(), 0);
 
-        submarine.change_position_from("data/day2/test.txt");
+        submarine
+            .change_position_from("data/day2/test.txt")
+            .unwrap();
         assert_eq!(submarine.position_hash(), 150);
         assert_eq!(submarine.position_h, 15);
         assert_eq!(submarine.depth, 10);
@@ -180,7 +244,9 @@ mod tests {
         let mut submarine = AimedSubmarine::new();
         assert_eq!(submarine.position_hash(), 0);
 
-        submarine.change_position_from("data/day2/test.txt");
+        submarine
+            .change_position_from("data/day2/test.txt")
+            .unwrap();
         assert_eq!(submarine.position_hash(), 900);
         assert_eq!(submarine.position_h, 15);
         assert_eq!(submarine.depth, 60);
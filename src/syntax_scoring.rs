@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::solution::{Answer, Solution};
+
+/// Result of checking the chunks in one line of the navigation subsystem.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LineStatus {
+    /// All chunks are closed.
+    Complete,
+    /// A chunk is closed with the wrong character `illegal` at zero-based
+    /// `column`.
+    Corrupted { illegal: char, column: usize },
+    /// Some chunks are still open. Appending `completion` closes them.
+    Incomplete { completion: String },
+}
+
+/// Lines of chunks in the navigation subsystem, made of `()`, `[]`, `{}`
+/// and `<>`.
+#[derive(Debug)]
+pub struct NavigationSubsystem {
+    lines: Vec<String>,
+}
+
+/// Closing character for the opening character `open` of a chunk.
+fn closing(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+impl LineStatus {
+    /// Checks the chunks in `line`, which may only consist of chunk
+    /// characters.
+    pub fn check(line: &str) -> LineStatus {
+        // Closing characters of all chunks, which are still open
+        let mut expected: Vec<char> = vec![];
+
+        for (column, char) in line.chars().enumerate() {
+            if let Some(close) = closing(char) {
+                expected.push(close);
+            } else if expected.pop() != Some(char) {
+                return LineStatus::Corrupted {
+                    illegal: char,
+                    column,
+                };
+            }
+        }
+
+        if expected.is_empty() {
+            LineStatus::Complete
+        } else {
+            LineStatus::Incomplete {
+                completion: expected.iter().rev().collect(),
+            }
+        }
+    }
+
+    /// Points for the first illegal character of a corrupted line.
+    pub fn syntax_error_score(&self) -> u64 {
+        match self {
+            LineStatus::Corrupted { illegal, .. } => match illegal {
+                ')' => 3,
+                ']' => 57,
+                '}' => 1197,
+                '>' => 25137,
+                _ => 0,
+            },
+            _ => 0,
+        }
+    }
+
+    /// Score of the completion string of an incomplete line.
+    pub fn completion_score(&self) -> Option<u64> {
+        match self {
+            LineStatus::Incomplete { completion } => {
+                Some(completion.chars().fold(0, |score, char| {
+                    let points = match char {
+                        ')' => 1,
+                        ']' => 2,
+                        '}' => 3,
+                        _ => 4,
+                    };
+                    score * 5 + points
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl NavigationSubsystem {
+    /// Status of every line.
+    pub fn check(&self) -> Vec<LineStatus> {
+        self.lines
+            .iter()
+            .map(|line| LineStatus::check(line))
+            .collect()
+    }
+
+    /// Sum of the syntax error scores of all corrupted lines.
+    pub fn syntax_error_score(&self) -> u64 {
+        self.check()
+            .iter()
+            .map(LineStatus::syntax_error_score)
+            .sum()
+    }
+
+    /// Middle score of all completion strings of incomplete lines.
+    pub fn middle_completion_score(&self) -> Option<u64> {
+        let mut scores: Vec<u64> = self
+            .check()
+            .iter()
+            .filter_map(LineStatus::completion_score)
+            .collect();
+        scores.sort_unstable();
+        scores.get(scores.len() / 2).copied()
+    }
+}
+
+impl FromStr for NavigationSubsystem {
+    type Err = Error;
+
+    /// Parses one line of chunks per line of `input`.
+    fn from_str(input: &str) -> Result<NavigationSubsystem> {
+        let mut lines = vec![];
+
+        for (idx, line) in input.lines().enumerate() {
+            if let Some((column, char)) = line
+                .chars()
+                .enumerate()
+                .find(|&(_, char)| !"()[]{}<>".contains(char))
+            {
+                return Err(Error::at(
+                    idx,
+                    column,
+                    format!("Expected a chunk character, got {:?}", char),
+                ));
+            }
+            lines.push(line.to_string());
+        }
+
+        Ok(NavigationSubsystem { lines })
+    }
+}
+
+impl Solution for NavigationSubsystem {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.syntax_error_score().into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let score = self
+            .middle_completion_score()
+            .ok_or_else(|| Error::invalid("No line is incomplete."))?;
+        Ok(Some(score.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn classify_lines() {
+        assert_eq!(LineStatus::check("([]){<>}"), LineStatus::Complete);
+        assert_eq!(
+            LineStatus::check("{([(<{}[<>[]}>{[]{[(<()>"),
+            LineStatus::Corrupted {
+                illegal: '}',
+                column: 12
+            }
+        );
+
+        let status = LineStatus::check("[({(<(())[]>[[{[]{<()<>>");
+        assert_eq!(
+            status,
+            LineStatus::Incomplete {
+                completion: "}}]])})]".to_string()
+            }
+        );
+        assert_eq!(status.completion_score(), Some(288957));
+    }
+
+    #[test]
+    fn score_subsystem() {
+        let subsystem =
+            NavigationSubsystem::from_file("data/day10/test.txt").unwrap();
+        assert_eq!(subsystem.syntax_error_score(), 26397);
+        assert_eq!(subsystem.middle_completion_score(), Some(288957));
+
+        let complete = NavigationSubsystem::parse("()\n{<>}").unwrap();
+        assert_eq!(
+            complete.part_two().unwrap_err().to_string(),
+            "No line is incomplete."
+        );
+    }
+
+    #[test]
+    fn reject_other_characters() {
+        assert_eq!(
+            "()\n(a)"
+                .parse::<NavigationSubsystem>()
+                .unwrap_err()
+                .to_string(),
+            "2:2: Expected a chunk character, got 'a'"
+        );
+        assert_eq!(
+            "(é)"
+                .parse::<NavigationSubsystem>()
+                .unwrap_err()
+                .to_string(),
+            "1:2: Expected a chunk character, got 'é'"
+        );
+    }
+}
@@ -1,99 +1,44 @@
-use std::{collections::VecDeque, path::Path};
+use std::{collections::VecDeque, fmt, str::FromStr};
 
-use crate::file_handler::read_lines;
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::grid::{Grid, Position};
+use crate::solution::{Answer, Solution};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum FoldingInstruction {
     FoldUp(usize),
     FoldLeft(usize),
 }
 
-impl From<String> for FoldingInstruction {
-    fn from(s: String) -> Self {
-        let number: usize = s
-            .split('=')
-            .nth(1)
-            .expect("Error getting number of folding instruction")
-            .parse()
-            .expect(
-                "Error converting number of folding instruction to integer.",
-            );
-
-        if s.starts_with("fold along y=") {
-            FoldingInstruction::FoldUp(number)
-        } else if s.starts_with("fold along x=") {
-            FoldingInstruction::FoldLeft(number)
+impl FromStr for FoldingInstruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(number) = s.strip_prefix("fold along y=") {
+            Ok(FoldingInstruction::FoldUp(parse_token(number, 13)?))
+        } else if let Some(number) = s.strip_prefix("fold along x=") {
+            Ok(FoldingInstruction::FoldLeft(parse_token(number, 13)?))
         } else {
-            panic!("Encountered unknown folding instruction {}", s);
+            Err(Error::parse(
+                0,
+                format!("Encountered unknown folding instruction {}", s),
+            ))
         }
     }
 }
 
+#[derive(Clone)]
 pub struct TransparentPaper {
-    // True, if position x, y is marked
-    markings: Vec<Vec<bool>>,
+    // True, if position (y, x) is marked
+    markings: Grid<bool>,
     instructions: VecDeque<FoldingInstruction>,
 }
 
 impl TransparentPaper {
-    pub fn from_file<P>(file: P) -> TransparentPaper
-    where
-        P: AsRef<Path>,
-    {
-        let mut markings_list: Vec<(usize, usize)> = vec![];
-        let mut dimension_x: usize = 0;
-        let mut dimension_y: usize = 0;
-        let mut instructions: VecDeque<FoldingInstruction> = VecDeque::new();
-
-        let lines = read_lines(file).expect("Error reading file");
-        for line in lines {
-            let line = line.expect("Could not parse line");
-
-            if line.is_empty() {
-                continue;
-            }
-
-            if line.starts_with("fold") {
-                instructions.push_back(FoldingInstruction::from(line));
-            } else {
-                let coordinate = line.split(',').collect::<Vec<&str>>();
-
-                let x: usize = coordinate[0]
-                    .parse()
-                    .expect("Error parsing x-coordinate.");
-                let y: usize = coordinate[1]
-                    .parse()
-                    .expect("Error parsing y-coordinate.");
-
-                dimension_x = dimension_x.max(x);
-                dimension_y = dimension_y.max(y);
-
-                markings_list.push((x, y));
-            }
-        }
-
-        let mut markings: Vec<Vec<bool>> =
-            vec![vec![false; dimension_y + 1]; dimension_x + 1];
-        for (x, y) in markings_list {
-            markings[x][y] = true;
-        }
-
-        TransparentPaper {
-            markings,
-            instructions,
-        }
-    }
-
     /// Folds the paper consuming one instruction.
     /// Returns `true` if folded, returns `false` if no instructionw as left.
     pub fn fold(&mut self) -> bool {
         if let Some(instruction) = self.instructions.pop_front() {
-            println!(
-                "Paper dimension is ({}, {}). Folding with instruction {:?}.",
-                self.width(),
-                self.height(),
-                instruction
-            );
             self.fold_with_instruction(instruction);
             true
         } else {
@@ -108,98 +53,134 @@ impl TransparentPaper {
         }
     }
 
+    /// Folds the part below row `y` up. The paper keeps the positions above
+    /// the fold-line, which are marked if they or their mirror image below
+    /// the line are marked.
     fn fold_up(&mut self, y: usize) {
-        let mut new_markings = vec![vec![false; y]; self.width()];
+        self.markings =
+            Grid::from_fn(self.markings.width(), y, |(row, col)| {
+                self.is_marked((row, col))
+                    || self.is_marked((2 * y - row, col))
+            });
+    }
 
-        for (x, column) in self.markings.iter().enumerate() {
-            // Make a new, shorter column with all the items above & excluding the fold-line
-            let mut new_column: Vec<bool> = column.clone()[0..y].to_vec();
+    /// Folds the part right of column `x` to the left, see `fold_up`.
+    fn fold_left(&mut self, x: usize) {
+        self.markings =
+            Grid::from_fn(x, self.markings.height(), |(row, col)| {
+                self.is_marked((row, col))
+                    || self.is_marked((row, 2 * x - col))
+            });
+    }
 
-            // Fold items below the fold-line upwards.
-            for (idx, marked) in column.iter().skip(y + 1).enumerate() {
-                let target_idx = 2 * y - (idx + y + 1);
-                new_column[target_idx] = *marked || new_column[target_idx];
-            }
+    fn is_marked(&self, position: Position) -> bool {
+        self.markings.get(position).copied().unwrap_or(false)
+    }
 
-            new_markings[x] = new_column;
-        }
+    /// Returns number of marked fields
+    pub fn num_marked(&self) -> usize {
+        self.markings.iter().filter(|item| **item).count()
+    }
 
-        self.markings = new_markings;
+    /// Prints the paper
+    pub fn dump(&self) {
+        println!("{}", self);
     }
+}
 
-    fn fold_left(&mut self, x: usize) {
-        let start = 2 * x - (self.width() - 1);
-        let mut new_markings = vec![vec![false; self.height()]; x];
+/// Parses a marking with coordinates `x,y`.
+fn parse_marking(line: &str) -> Result<(usize, usize)> {
+    let (x, y) = line.split_once(',').ok_or_else(|| {
+        Error::parse(0, format!("Expected coordinate x,y, got {:?}", line))
+    })?;
 
-        for (idx, column) in self.markings.iter().enumerate() {
-            if idx >= x {
+    Ok((parse_token(x, 0)?, parse_token(y, column_of(line, y))?))
+}
+
+/// Renders the paper line by line, `#` for marked and `.` for unmarked
+/// positions.
+impl fmt::Display for TransparentPaper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.markings
+            .map(|&marked| if marked { '#' } else { '.' })
+            .fmt(f)
+    }
+}
+
+impl FromStr for TransparentPaper {
+    type Err = Error;
+
+    /// Parses the coordinates `x,y` of all markings, one per line, followed
+    /// by the folding instructions.
+    fn from_str(lines: &str) -> Result<TransparentPaper> {
+        let mut markings_list: Vec<(usize, usize)> = vec![];
+        let mut dimension_x: usize = 0;
+        let mut dimension_y: usize = 0;
+        let mut instructions: VecDeque<FoldingInstruction> = VecDeque::new();
+
+        for (idx, line) in lines.lines().enumerate() {
+            if line.is_empty() {
                 continue;
             }
 
-            let new_column: Vec<bool>;
-
-            if idx < start {
-                new_column = column.to_vec();
+            if line.starts_with("fold") {
+                instructions.push_back(
+                    line.parse().map_err(|e: Error| e.offset_lines(idx))?,
+                );
             } else {
-                let folded_column = self.markings[2 * x - idx].to_vec();
-                new_column = column
-                    .iter()
-                    .zip(folded_column.iter())
-                    .map(|(&a, &b)| a || b)
-                    .collect();
+                let (x, y) =
+                    parse_marking(line).map_err(|e| e.offset_lines(idx))?;
+
+                dimension_x = dimension_x.max(x);
+                dimension_y = dimension_y.max(y);
+
+                markings_list.push((x, y));
             }
+        }
 
-            new_markings[idx] = new_column;
+        if markings_list.is_empty() {
+            return Err(Error::parse(0, "Paper has no markings."));
         }
 
-        self.markings = new_markings;
-    }
+        let mut markings = Grid::new(dimension_x + 1, dimension_y + 1, false);
+        for (x, y) in markings_list {
+            markings[(y, x)] = true;
+        }
 
-    fn height(&self) -> usize {
-        self.markings[0].len()
+        Ok(TransparentPaper {
+            markings,
+            instructions,
+        })
     }
+}
 
-    fn width(&self) -> usize {
-        self.markings.len()
+impl Solution for TransparentPaper {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
     }
 
-    /// Returns number of marked fields
-    pub fn num_marked(&self) -> usize {
-        self.markings.iter().flatten().filter(|item| **item).count()
+    fn part_one(&self) -> Result<Answer> {
+        let mut paper = self.clone();
+        paper.fold();
+        Ok(paper.num_marked().into())
     }
 
-    /// Prints the paper
-    pub fn dump(&self) {
-        // This is done very badle... :/
-        let mut transposed: Vec<Vec<bool>> =
-            vec![vec![false; self.width()]; self.height()];
-
-        for (x, column) in self.markings.iter().enumerate() {
-            for (y, &marked) in column.iter().enumerate() {
-                transposed[y][x] = marked;
-            }
-        }
-
-        for line in transposed {
-            for marked in line {
-                if marked {
-                    print!("#")
-                } else {
-                    print!(".")
-                }
-            }
-            println!();
-        }
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let mut paper = self.clone();
+        while paper.fold() {}
+        Ok(Some(paper.to_string().into()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_handler::FromInput;
 
     #[test]
     fn test_first_fold() {
-        let mut paper = TransparentPaper::from_file("data/day13/test.txt");
+        let mut paper =
+            TransparentPaper::from_file("data/day13/test.txt").unwrap();
         paper.dump();
         paper.fold();
         paper.dump();
@@ -208,7 +189,8 @@ mod tests {
 
     #[test]
     fn test_two_folds() {
-        let mut paper = TransparentPaper::from_file("data/day13/test.txt");
+        let mut paper =
+            TransparentPaper::from_file("data/day13/test.txt").unwrap();
         paper.dump();
         paper.fold();
         paper.dump();
@@ -216,4 +198,24 @@ mod tests {
         paper.dump();
         assert_eq!(paper.num_marked(), 16);
     }
+
+    #[test]
+    fn reject_invalid_lines() {
+        assert_eq!(
+            parse_marking("3,y").unwrap_err().to_string(),
+            "1:3: Could not parse number \"y\""
+        );
+        assert!("fold along z=3".parse::<FoldingInstruction>().is_err());
+    }
+
+    #[test]
+    fn render_folded_paper() {
+        let mut paper =
+            TransparentPaper::from_file("data/day13/test.txt").unwrap();
+        while paper.fold() {}
+        assert_eq!(
+            paper.to_string(),
+            "#####\n#...#\n#...#\n#...#\n#####\n.....\n....."
+        );
+    }
 }
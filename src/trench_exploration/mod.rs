@@ -1,11 +1,15 @@
 use std::ops::RangeInclusive;
 mod probe_launcher;
-pub mod types;
-use probe_launcher::ProbeLauncher;
+mod types;
+use crate::error::{column_of, parse_token, Error, Result};
+use crate::solution::{Answer, Solution};
+pub use probe_launcher::ProbeLauncher;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use types::Velocity;
+use std::str::FromStr;
+pub use types::{Position, Velocity};
 
+#[derive(Debug)]
 pub struct ProbeLauncherSimulation<T> {
     target_x: RangeInclusive<T>,
     target_y: RangeInclusive<T>,
@@ -20,7 +24,8 @@ impl ProbeLauncherSimulation<i32> {
     }
 
     // Returns achieved maximum height and number of initial velocities that land in target area.
-    pub fn initial_velocity_for_highest_shot(&self) -> (i32, usize) {
+    // Errors if no initial velocity hits the target area.
+    pub fn initial_velocity_for_highest_shot(&self) -> Result<(i32, usize)> {
         let vx_range = 0..=*self.target_x.end();
         // Actually not sure if this is correct.
         let vy_range = *self.target_y.start()..=*self.target_y.start() * (-1);
@@ -30,7 +35,7 @@ impl ProbeLauncherSimulation<i32> {
         let mut hitting_velocities: HashSet<Velocity> = HashSet::new();
 
         for vx in vx_range.into_iter() {
-            for vy in vy_range.clone().into_iter() {
+            for vy in vy_range.clone() {
                 let initial_v = Velocity::from((vx, vy));
                 let mut highest_y: i32 = 0;
                 let mut launcher = ProbeLauncher::new(initial_v);
@@ -61,17 +66,90 @@ impl ProbeLauncherSimulation<i32> {
             }
         }
 
-        let highest_y = hitting_velocities_by_height
-            .keys()
-            .max()
-            .expect("Did not find any initial velocity hitting the target!");
-        (*highest_y, hitting_velocities.len())
+        let highest_y =
+            hitting_velocities_by_height.keys().max().ok_or_else(|| {
+                Error::invalid("No initial velocity hits the target area.")
+            })?;
+        Ok((*highest_y, hitting_velocities.len()))
+    }
+}
+
+impl FromStr for ProbeLauncherSimulation<i32> {
+    type Err = Error;
+
+    /// Parses the target area described in the first line of `input`, e.g.
+    /// `target area: x=20..30, y=-10..-5`. The target area has to be below
+    /// the launcher at y=0.
+    fn from_str(input: &str) -> Result<Self> {
+        let description = input.lines().next().unwrap_or_default();
+        let invalid = || {
+            Error::parse(
+                0,
+                format!(
+                    "Expected target area: x=A..B, y=C..D, got {:?}",
+                    description
+                ),
+            )
+        };
+
+        let (x, y) = description
+            .strip_prefix("target area: x=")
+            .and_then(|ranges| ranges.split_once(", y="))
+            .ok_or_else(invalid)?;
+
+        let parse_range = |range: &str| -> Result<RangeInclusive<i32>> {
+            let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+            let column = column_of(description, start);
+            let range = parse_token(start, column)?
+                ..=parse_token(end, column_of(description, end))?;
+            if range.is_empty() {
+                return Err(Error::parse(
+                    column,
+                    format!(
+                        "Expected range from low to high, got {:?}",
+                        range
+                    ),
+                ));
+            }
+            Ok(range)
+        };
+
+        let target_x = parse_range(x)?;
+        let target_y = parse_range(y)?;
+        if *target_y.end() >= 0 {
+            return Err(Error::parse(
+                column_of(description, y),
+                format!(
+                    "Expected target area below y=0, got y={:?}",
+                    target_y
+                ),
+            ));
+        }
+
+        Ok(Self::new(target_x, target_y))
+    }
+}
+
+impl Solution for ProbeLauncherSimulation<i32> {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        let (highest_y, _) = self.initial_velocity_for_highest_shot()?;
+        Ok(highest_y.into())
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        let (_, v_count) = self.initial_velocity_for_highest_shot()?;
+        Ok(Some(v_count.into()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_handler::FromInput;
 
     #[test]
     fn max_height() {
@@ -79,7 +157,7 @@ mod tests {
             target_x: 20..=30,
             target_y: -10..=-5,
         };
-        let (y, _) = sim.initial_velocity_for_highest_shot();
+        let (y, _) = sim.initial_velocity_for_highest_shot().unwrap();
         assert_eq!(y, 45);
     }
 
@@ -89,7 +167,44 @@ mod tests {
             target_x: 20..=30,
             target_y: -10..=-5,
         };
-        let (_, count) = sim.initial_velocity_for_highest_shot();
+        let (_, count) = sim.initial_velocity_for_highest_shot().unwrap();
         assert_eq!(count, 112);
     }
+
+    #[test]
+    fn target_area_from_file() {
+        let sim =
+            ProbeLauncherSimulation::from_file("data/day17/test.txt").unwrap();
+        assert_eq!(sim.target_x, 20..=30);
+        assert_eq!(sim.target_y, -10..=-5);
+
+        let error = "target area: x=20..30, y=-10..five"
+            .parse::<ProbeLauncherSimulation<i32>>()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "1:31: Could not parse number \"five\"");
+    }
+
+    #[test]
+    fn reject_invalid_target_area() {
+        let error = |input: &str| {
+            input
+                .parse::<ProbeLauncherSimulation<i32>>()
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("target area: x=5..1, y=-10..-5"),
+            "1:16: Expected range from low to high, got 5..=1"
+        );
+        assert_eq!(
+            error("target area: x=20..30, y=1..3"),
+            "1:26: Expected target area below y=0, got y=1..=3"
+        );
+
+        let sim = ProbeLauncherSimulation::new(-30..=-20, -10..=-5);
+        assert_eq!(
+            sim.part_one().unwrap_err().to_string(),
+            "No initial velocity hits the target area."
+        );
+    }
 }
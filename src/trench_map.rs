@@ -0,0 +1,251 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::solution::{Answer, Solution};
+
+/// Number of entries in an image enhancement algorithm, one for each
+/// combination of 3×3 pixels.
+const ALGORITHM_SIZE: usize = 512;
+
+/// Infinite image of light and dark pixels.
+///
+/// Only a finite window of pixels is stored. All pixels outside of it
+/// share the same `background`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+/// Image enhancement algorithm and the input image of the trench map.
+#[derive(Debug)]
+pub struct TrenchMap {
+    /// `algorithm[i]` is the output pixel for the 3×3 input pixels, which
+    /// read as binary number `i` row by row, with light pixels as 1.
+    algorithm: [bool; ALGORITHM_SIZE],
+    image: Image,
+}
+
+/// Parses `#` as a light pixel and `.` as a dark one.
+fn parse_pixel(char: char) -> std::result::Result<bool, String> {
+    match char {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(format!("Expected pixel # or ., got {:?}", char)),
+    }
+}
+
+impl Image {
+    /// Pixel at `row` and `col`, which may be outside of the stored window.
+    fn pixel(&self, row: isize, col: isize) -> bool {
+        if row < 0 || col < 0 {
+            return self.background;
+        }
+
+        self.pixels
+            .get((row as usize, col as usize))
+            .copied()
+            .unwrap_or(self.background)
+    }
+
+    /// Applies the enhancement `algorithm` to every pixel at once.
+    ///
+    /// Only the pixels next to the window can change differently from the
+    /// background, so the window grows by one pixel on every side. The
+    /// background itself turns into the output for 9 background pixels. If
+    /// `algorithm[0]` is light, a dark background lights up, and switches
+    /// back if `algorithm[511]` is dark.
+    pub fn enhance(&self, algorithm: &[bool; ALGORITHM_SIZE]) -> Image {
+        let (width, height) = (self.pixels.width(), self.pixels.height());
+        let pixels = Grid::from_fn(width + 2, height + 2, |(row, col)| {
+            // Shift by the new border, and by 1 to the top-left neighbour
+            let (row, col) = (row as isize - 2, col as isize - 2);
+            let index = (0..3)
+                .flat_map(|dr| (0..3).map(move |dc| (row + dr, col + dc)))
+                .fold(0, |index, (r, c)| {
+                    index << 1 | usize::from(self.pixel(r, c))
+                });
+            algorithm[index]
+        });
+
+        let background = if self.background {
+            algorithm[ALGORITHM_SIZE - 1]
+        } else {
+            algorithm[0]
+        };
+
+        Image { pixels, background }
+    }
+
+    /// Number of light pixels, or `None` if infinitely many pixels of the
+    /// background are light.
+    pub fn lit_count(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+
+        Some(self.pixels.iter().filter(|&&lit| lit).count())
+    }
+
+    pub fn dump(&self) {
+        println!("{}", self);
+    }
+}
+
+/// Renders the stored window with `#` for light pixels and `.` for dark
+/// ones.
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pixels.map(|&lit| if lit { '#' } else { '.' }).fmt(f)
+    }
+}
+
+impl FromStr for Image {
+    type Err = Error;
+
+    /// Parses a finite image on a dark background.
+    fn from_str(input: &str) -> Result<Image> {
+        Ok(Image {
+            pixels: Grid::parse_with(input, parse_pixel)?,
+            background: false,
+        })
+    }
+}
+
+impl TrenchMap {
+    /// The input image after `passes` enhancements.
+    pub fn enhanced(&self, passes: usize) -> Image {
+        (0..passes).fold(self.image.clone(), |image, _| {
+            image.enhance(&self.algorithm)
+        })
+    }
+
+    /// Light pixels after `passes` enhancements, as an answer.
+    fn lit_after(&self, passes: usize) -> Answer {
+        match self.enhanced(passes).lit_count() {
+            Some(count) => count.into(),
+            None => "infinitely many".to_string().into(),
+        }
+    }
+}
+
+impl FromStr for TrenchMap {
+    type Err = Error;
+
+    /// Parses the algorithm of 512 pixels in the first line, followed by an
+    /// empty line and the input image.
+    fn from_str(input: &str) -> Result<TrenchMap> {
+        let mut lines = input.lines();
+
+        let algorithm = lines
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .enumerate()
+            .map(|(column, char)| {
+                parse_pixel(char).map_err(|e| Error::parse(column, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let algorithm: [bool; ALGORITHM_SIZE] =
+            algorithm.try_into().map_err(|algorithm: Vec<bool>| {
+                Error::parse(
+                    0,
+                    format!(
+                        "Expected an algorithm of {} pixels, got {}",
+                        ALGORITHM_SIZE,
+                        algorithm.len()
+                    ),
+                )
+            })?;
+
+        if !lines.next().unwrap_or_default().trim().is_empty() {
+            return Err(Error::at(1, 0, "Expected an empty line"));
+        }
+        let image = lines
+            .collect::<Vec<_>>()
+            .join("\n")
+            .parse::<Image>()
+            .map_err(|e| e.offset_lines(2))?;
+
+        Ok(TrenchMap { algorithm, image })
+    }
+}
+
+impl Solution for TrenchMap {
+    fn parse(input: &str) -> Result<Self> {
+        input.parse()
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.lit_after(2))
+    }
+
+    fn part_two(&self) -> Result<Option<Answer>> {
+        Ok(Some(self.lit_after(50)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_handler::FromInput;
+
+    #[test]
+    fn enhance_image() {
+        let map = TrenchMap::from_file("data/day20/test.txt").unwrap();
+        let image = map.enhanced(2);
+        assert_eq!(
+            image.to_string(),
+            ".......#.\n\
+             .#..#.#..\n\
+             #.#...###\n\
+             #...##.#.\n\
+             #.....#.#\n\
+             .#.#####.\n\
+             ..#.#####\n\
+             ...##.##.\n\
+             ....###.."
+        );
+        assert_eq!(image.lit_count(), Some(35));
+        assert_eq!(map.enhanced(50).lit_count(), Some(3351));
+    }
+
+    #[test]
+    fn toggle_background() {
+        // Inverts every pixel, by lighting up exactly the 3×3 pixels with a
+        // dark center
+        let algorithm: [bool; ALGORITHM_SIZE] =
+            std::array::from_fn(|index| index & 1 << 4 == 0);
+        let image: Image = "#.\n.#".parse().unwrap();
+
+        let inverted = image.enhance(&algorithm);
+        assert_eq!(inverted.to_string(), "####\n#.##\n##.#\n####");
+        assert_eq!(inverted.lit_count(), None);
+
+        let restored = inverted.enhance(&algorithm);
+        assert_eq!(
+            restored.to_string(),
+            "......\n......\n..#...\n...#..\n......\n......"
+        );
+        assert_eq!(restored.lit_count(), Some(2));
+    }
+
+    #[test]
+    fn reject_invalid_input() {
+        assert_eq!(
+            "#.#\n\n#.".parse::<TrenchMap>().unwrap_err().to_string(),
+            "1:1: Expected an algorithm of 512 pixels, got 3"
+        );
+
+        let algorithm = ".".repeat(ALGORITHM_SIZE);
+        assert_eq!(
+            format!("{}\n\n#.\n.x", algorithm)
+                .parse::<TrenchMap>()
+                .unwrap_err()
+                .to_string(),
+            "4:2: Expected pixel # or ., got 'x'"
+        );
+    }
+}
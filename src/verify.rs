@@ -0,0 +1,185 @@
+use crate::cli::Part;
+use aoc2021::{
+    read_all, registry::Day, Answer, Error, ExpectedAnswers, Result,
+};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Result of checking the answer to one part against the manifest.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The part is not solved or has no answer, although an answer is
+    /// expected.
+    Fail(String),
+    /// The manifest has no answer for this part.
+    Unchecked,
+}
+
+/// Counts of outcomes over all checked answers.
+#[derive(Debug, Default)]
+pub struct Tally {
+    pub passed: usize,
+    pub mismatched: usize,
+    pub failed: usize,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Mismatch { expected, actual } => {
+                write!(
+                    f,
+                    "MISMATCH, expected {:?}, got {:?}",
+                    expected, actual
+                )
+            }
+            Outcome::Fail(reason) => write!(f, "FAIL, {}", reason),
+            Outcome::Unchecked => write!(f, "no expected answer"),
+        }
+    }
+}
+
+impl Tally {
+    pub fn add(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pass => self.passed += 1,
+            Outcome::Mismatch { .. } => self.mismatched += 1,
+            Outcome::Fail(_) => self.failed += 1,
+            Outcome::Unchecked => (),
+        }
+    }
+
+    /// True, if any answer changed or could not be computed.
+    pub fn has_regressions(&self) -> bool {
+        self.mismatched > 0 || self.failed > 0
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Verified {} answers: {} passed, {} mismatched, {} failed.",
+            self.passed + self.mismatched + self.failed,
+            self.passed,
+            self.mismatched,
+            self.failed
+        )
+    }
+}
+
+/// Input files of `day`, which have an answers manifest, ordered by name.
+pub fn inputs_with_answers(day: &Day) -> Result<Vec<PathBuf>> {
+    let dir = day.data_dir();
+    let entries =
+        fs::read_dir(&dir).map_err(|e| Error::from(e).in_file(&dir))?;
+
+    let mut inputs = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| Error::from(e).in_file(&dir))?.path();
+        if path.extension().is_some_and(|extension| extension == "txt")
+            && ExpectedAnswers::manifest_for(&path).is_file()
+        {
+            inputs.push(path);
+        }
+    }
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Solves the selected `part` of `day` on the puzzle input in `file` and
+/// compares the answers with the ones in the manifest of the file.
+pub fn check(
+    day: &Day,
+    file: &Path,
+    part: Option<Part>,
+) -> Result<Vec<(Part, Outcome)>> {
+    let manifest = ExpectedAnswers::manifest_for(file);
+    let expected: ExpectedAnswers = read_all(&manifest)?
+        .parse()
+        .map_err(|e: Error| e.in_file(&manifest))?;
+    let solution = day.parse(&read_all(file)?).map_err(|e| e.in_file(file))?;
+
+    let mut outcomes = vec![];
+    if Part::One.is_selected(part) {
+        let outcome =
+            compare(&expected.part_one, solution.part_one().map(Some));
+        outcomes.push((Part::One, outcome));
+    }
+    if Part::Two.is_selected(part) {
+        let outcome = compare(&expected.part_two, solution.part_two());
+        outcomes.push((Part::Two, outcome));
+    }
+
+    Ok(outcomes)
+}
+
+fn compare(
+    expected: &Option<String>,
+    answer: Result<Option<Answer>>,
+) -> Outcome {
+    match (expected, answer) {
+        (None, _) => Outcome::Unchecked,
+        (Some(_), Err(e)) => Outcome::Fail(e.to_string()),
+        (Some(_), Ok(None)) => Outcome::Fail("not solved yet".to_string()),
+        (Some(expected), Ok(Some(answer))) => {
+            if ExpectedAnswers::matches(expected, &answer) {
+                Outcome::Pass
+            } else {
+                Outcome::Mismatch {
+                    expected: expected.clone(),
+                    actual: answer.to_string(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc2021::registry;
+
+    #[test]
+    fn compare_answers() {
+        let expected = Some("42".to_string());
+        assert_eq!(
+            compare(&expected, Ok(Some(Answer::Signed(42)))),
+            Outcome::Pass
+        );
+        assert_eq!(
+            compare(&expected, Ok(Some(Answer::Unsigned(41)))),
+            Outcome::Mismatch {
+                expected: "42".to_string(),
+                actual: "41".to_string()
+            }
+        );
+        assert!(matches!(compare(&expected, Ok(None)), Outcome::Fail(_)));
+        assert_eq!(
+            compare(&expected, Err(Error::invalid("No answer"))),
+            Outcome::Fail("No answer".to_string())
+        );
+        assert_eq!(compare(&None, Ok(None)), Outcome::Unchecked);
+    }
+
+    #[test]
+    fn verify_test_input() {
+        let day = registry::find(17).unwrap();
+        let inputs = inputs_with_answers(day).unwrap();
+        assert!(inputs.contains(&PathBuf::from("data/day17/test.txt")));
+
+        let outcomes = check(day, Path::new("data/day17/test.txt"), None);
+        assert_eq!(
+            outcomes.unwrap(),
+            vec![(Part::One, Outcome::Pass), (Part::Two, Outcome::Pass)]
+        );
+    }
+}